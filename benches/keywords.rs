@@ -0,0 +1,71 @@
+#![feature(test)]
+extern crate test;
+
+use std::rc::Rc;
+use test::Bencher;
+use volt_parse::prelude::*;
+
+const KEYWORDS : &[&str] = &[
+    "select", "from", "where", "group", "by", "having", "order", "limit", "offset", "insert", "into", "values",
+    "update", "set", "delete", "create", "table", "drop", "alter", "index", "join", "inner", "outer", "left", "right",
+    "on", "as", "and", "or", "not", "null", "is", "in", "like", "between", "distinct", "union", "all", "case", "when",
+    "then", "else", "end", "=", "==", "!=", "<", "<=", "<<", ">", ">=", ">>", "(", ")", ",", "*",
+];
+
+fn input() -> String
+{
+    let mut text = String::new();
+    for i in 0..2000
+    {
+        text.push_str(KEYWORDS[(i * 7) % KEYWORDS.len()]);
+        text.push(' ');
+    }
+    text
+}
+
+// Counts the space separated keywords, stepping through the input directly so only the keyword parser is measured
+fn count<'a>(keyword : impl Parser<'a, String>, text : &'a str) -> usize
+{
+    let token = then(keyword, char_single(' '), take_left);
+    let mut ind = ParserInput::new(text);
+    let mut count = 0;
+    while let Ok(res) = token(&ind)
+    {
+        ind = res.to_in();
+        count += 1;
+    }
+    count
+}
+
+#[bench]
+fn one_of_keywords_bench(b : &mut Bencher)
+{
+    let text = input();
+    let keyword = one_of_keywords(KEYWORDS);
+    b.iter(|| assert_eq!(count(keyword.clone(), &text), 2000));
+}
+
+type DynParser<'a> = Rc<dyn Fn(&ParserInput<'a>) -> POut<'a, String> + 'a>;
+
+// The same keywords as a chain of `or`, longest first so that the longest match is found
+fn nested_or<'a>(words : &[&'a str]) -> DynParser<'a>
+{
+    let mut words = words.to_vec();
+    words.sort_by_key(|w| std::cmp::Reverse(w.len()));
+
+    let mut parser : DynParser<'a> = Rc::new(keyword(words[0]));
+    for word in &words[1..]
+    {
+        let prev = parser.clone();
+        parser = Rc::new(or(move |ind : &ParserInput<'a>| prev(ind), keyword(word)));
+    }
+    parser
+}
+
+#[bench]
+fn nested_or_bench(b : &mut Bencher)
+{
+    let text = input();
+    let keyword = nested_or(KEYWORDS);
+    b.iter(|| assert_eq!(count(|ind : &ParserInput<'_>| keyword(ind), &text), 2000));
+}
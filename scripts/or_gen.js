@@ -29,8 +29,8 @@ function genOrParser(size) {
         ) -> impl Parser<'a, Or${size}< ${typeList} >>
         {
             move |ind : &ParserInput<'a>| -> POut<'a, Or${size}< ${typeList} >> {
-                a(ind).map(|ao| ao.with_val(Or${size}::A(ao.val.clone())))
-                ${letters.filter((l, i) => i > 0).map((l, i) => `.or_else(|_| ${l}(ind).map(|ao| ao.with_val(Or${size}::${l.toUpperCase()}(ao.val.clone()))))\n\t    `).join("")}
+                attempt(|| a(ind)).map(|ao| ao.with_val(Or${size}::A(ao.val.clone())))
+                ${letters.filter((l, i) => i > 0).map((l, i) => `.or_else(|_| attempt(|| ${l}(ind)).map(|ao| ao.with_val(Or${size}::${l.toUpperCase()}(ao.val.clone()))))\n\t    `).join("")}
             }
         }
 
@@ -1,3 +1,32 @@
 #![feature(trait_alias, type_alias_impl_trait)]
 pub mod prelude;
 pub mod volt_parse;
+pub use crate::volt_parse::balanced::*;
+pub use crate::volt_parse::char_class::*;
+pub use crate::volt_parse::combiner::*;
+pub use crate::volt_parse::combiners::*;
+pub use crate::volt_parse::cst::*;
+pub use crate::volt_parse::defs::*;
+pub use crate::volt_parse::derive::*;
+pub use crate::volt_parse::file_pos::*;
+pub use crate::volt_parse::formats;
+pub use crate::volt_parse::grammar;
+pub use crate::volt_parse::incremental::*;
+pub use crate::volt_parse::keywords::*;
+pub use crate::volt_parse::numbers::*;
+pub use crate::volt_parse::parser::*;
+pub use crate::volt_parse::parsers_core::*;
+pub use crate::volt_parse::parsers_core_ors::*;
+pub use crate::volt_parse::parsers_ext::*;
+pub use crate::volt_parse::paths;
+pub use crate::volt_parse::profile::*;
+#[cfg(feature = "regex")]
+pub use crate::volt_parse::regex::*;
+pub use crate::volt_parse::span::*;
+pub use crate::volt_parse::strings::*;
+pub use crate::volt_parse::trace::*;
+pub use crate::volt_parse::trivia::*;
+#[cfg(feature = "unicode")]
+pub use crate::volt_parse::unicode::*;
+#[cfg(feature = "macros")]
+pub use volt_parse_macros::{grammar, VoltParse};
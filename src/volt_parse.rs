@@ -1,10 +1,29 @@
+pub mod balanced;
+pub mod char_class;
 pub mod combiner;
 pub mod combiners;
+pub mod cst;
 pub mod defs;
+pub mod derive;
 pub mod file_pos;
+pub mod formats;
+pub mod grammar;
+pub mod incremental;
+pub mod keywords;
+pub mod numbers;
 pub mod parser;
 pub mod parsers_core;
 pub mod parsers_core_ors;
 pub mod parsers_ext;
+pub mod paths;
+pub mod profile;
+#[cfg(feature = "regex")]
+pub mod regex;
+pub mod span;
+pub mod strings;
 #[cfg(test)]
 mod tests;
+pub mod trace;
+pub mod trivia;
+#[cfg(feature = "unicode")]
+pub mod unicode;
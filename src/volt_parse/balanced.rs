@@ -0,0 +1,181 @@
+use super::{
+    parser::{PErr, POut, PRes, Parser, ParserInput},
+    strings::{string_literal, StringFormat},
+};
+
+// The bracket pairs that `balanced_with` tracks, and the string literals within which brackets are ignored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Brackets
+{
+    // Each as its opening and closing character
+    pub pairs :   Vec<(char, char)>,
+    pub strings : Vec<StringFormat>,
+}
+
+impl Brackets
+{
+    pub fn new(pairs : &[(char, char)]) -> Self
+    {
+        Self {
+            pairs :   pairs.to_vec(),
+            strings : vec![],
+        }
+    }
+
+    pub fn with_strings(mut self, format : StringFormat) -> Self
+    {
+        self.strings.push(format);
+        self
+    }
+}
+
+// Everything from `open` to its matching `close`, where any `open` within must be closed first, such as the whole of
+// `/* a /* b */ c */`, see `nested_with` to skip over string literals that may contain `close`
+//
+// Fails at the outermost `open` if it is never closed.
+#[inline]
+pub fn nested<'a>(open : &'a str, close : &'a str) -> impl Parser<'a, String> { nested_with(open, close, &[]) }
+
+// Like `nested`, but skipping over any string literal of the given formats within the region, such as the `"*/"` of
+// `/* s = "*/" */`
+//
+// Fails at the outermost `open` if it is never closed, or where a string within the region opens if the string is
+// never closed.
+#[inline]
+pub fn nested_with<'a>(open : &'a str, close : &'a str, strings : &[StringFormat]) -> impl Parser<'a, String>
+{
+    let strings = strings.to_vec();
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        let len = region_len(ind, open, close, &strings)?;
+        let matched = &ind.text[..len];
+        Ok(PRes {
+            val :       String::from(matched),
+            pos :       ind.pos.advance(matched),
+            remainder : &ind.text[len..],
+        })
+    }
+}
+
+// The length of the region beginning with `open` and ending at its matching `close`
+pub(super) fn nested_len(text : &str, open : &str, close : &str) -> Option<usize>
+{
+    region_len(&ParserInput::new(text), open, close, &[]).ok()
+}
+
+fn region_len(ind : &ParserInput, open : &str, close : &str, strings : &[StringFormat]) -> Result<usize, PErr>
+{
+    let text = ind.text;
+    let unclosed = PErr {
+        pos : ind.pos
+    };
+    if open.is_empty() || close.is_empty() || !text.starts_with(open)
+    {
+        return Err(unclosed);
+    }
+    let mut depth = 0;
+    let mut i = 0;
+    while i < text.len()
+    {
+        let rest = &text[i..];
+        if let Some(s) = strings
+            .iter()
+            .find(|s| depth > 0 && rest.starts_with(s.prefix) && rest[s.prefix.len()..].starts_with(s.quote))
+        {
+            let res = string_literal(*s)(&ParserInput {
+                text : rest,
+                pos :  ind.pos.advance(&text[..i]),
+            })?;
+            i += rest.len() - res.remainder.len();
+        }
+        else if rest.starts_with(open)
+        {
+            depth += 1;
+            i += open.len();
+        }
+        else if rest.starts_with(close)
+        {
+            depth -= 1;
+            i += close.len();
+            if depth == 0
+            {
+                return Ok(i);
+            }
+        }
+        else
+        {
+            i += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    Err(unclosed)
+}
+
+// A bracketed region of the input, such as `(a, {b: [c]})`, whose contents are skipped without being understood, see
+// `balanced_with` to skip over string literals that may contain brackets
+#[inline]
+pub fn balanced<'a>(pairs : &[(char, char)]) -> impl Parser<'a, String> { balanced_with(Brackets::new(pairs)) }
+
+// Fails at the outermost opening bracket if the region is never closed, or at a closing bracket that does not match the
+// bracket it would close
+#[inline]
+pub fn balanced_with<'a>(brackets : Brackets) -> impl Parser<'a, String>
+{
+    let strings = brackets
+        .strings
+        .iter()
+        .map(|s| (*s, string_literal(*s)))
+        .collect::<Vec<_>>();
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        let text = ind.text;
+        let err_at = |i : usize| PErr {
+            pos : ind.pos.advance(&text[..i]),
+        };
+        if !text.starts_with(|c| brackets.pairs.iter().any(|(open, _)| *open == c))
+        {
+            return Err(err_at(0));
+        }
+
+        // The closing bracket expected by each open bracket
+        let mut stack : Vec<char> = vec![];
+        let mut i = 0;
+        while let Some(c) = text[i..].chars().next()
+        {
+            let rest = &text[i..];
+            if let Some((_, p)) = strings
+                .iter()
+                .find(|(s, _)| rest.starts_with(s.prefix) && rest[s.prefix.len()..].starts_with(s.quote))
+            {
+                let res = p(&ParserInput {
+                    text : rest,
+                    pos :  ind.pos.advance(&text[..i]),
+                })?;
+                i += rest.len() - res.remainder.len();
+                continue;
+            }
+
+            if let Some((_, close)) = brackets.pairs.iter().find(|(open, _)| *open == c)
+            {
+                stack.push(*close);
+            }
+            else if brackets.pairs.iter().any(|(_, close)| *close == c)
+            {
+                match stack.pop()
+                {
+                    Some(expected) if expected == c => (),
+                    _ => return Err(err_at(i)),
+                }
+            }
+            i += c.len_utf8();
+
+            if stack.is_empty()
+            {
+                let matched = &text[..i];
+                return Ok(PRes {
+                    val :       String::from(matched),
+                    pos :       ind.pos.advance(matched),
+                    remainder : &text[i..],
+                });
+            }
+        }
+        Err(err_at(0))
+    }
+}
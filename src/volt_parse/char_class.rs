@@ -0,0 +1,139 @@
+use super::{parser::Parser, parsers_core::read_char_f};
+use std::ops::{BitAnd, BitOr, Not, Sub};
+#[cfg(feature = "unicode")]
+pub use unicode_general_category::GeneralCategory;
+#[cfg(feature = "unicode")]
+pub use unicode_script::Script;
+
+// A set of characters, built up from ranges and Unicode properties then combined with `|`, `&`, `-` and `!`:
+//
+//   let hex = CharClass::range('0', '9') | CharClass::range('a', 'f') | CharClass::range('A', 'F');
+//   let consonant = CharClass::range('a', 'z') - CharClass::chars("aeiou");
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharClass
+{
+    // Any character within any of the inclusive ranges
+    Ranges(Vec<(char, char)>),
+    Not(Box<CharClass>),
+    Union(Vec<CharClass>),
+    Intersection(Vec<CharClass>),
+    // Characters within the first class but not the second
+    Difference(Box<CharClass>, Box<CharClass>),
+    #[cfg(feature = "unicode")]
+    Category(GeneralCategory),
+    #[cfg(feature = "unicode")]
+    Script(Script),
+    // Characters that may begin an identifier, as defined by UAX #31
+    #[cfg(feature = "unicode")]
+    XidStart,
+    // Characters that may continue an identifier, as defined by UAX #31
+    #[cfg(feature = "unicode")]
+    XidContinue,
+}
+
+impl CharClass
+{
+    pub fn range(lo : char, hi : char) -> Self { CharClass::Ranges(vec![(lo, hi)]) }
+
+    pub fn chars(chars : &str) -> Self { CharClass::Ranges(chars.chars().map(|c| (c, c)).collect()) }
+
+    pub fn any() -> Self { Self::range('\0', char::MAX) }
+
+    pub fn none() -> Self { CharClass::Ranges(vec![]) }
+
+    pub fn contains(&self, c : char) -> bool
+    {
+        match self
+        {
+            CharClass::Ranges(ranges) => ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)),
+            CharClass::Not(class) => !class.contains(c),
+            CharClass::Union(classes) => classes.iter().any(|class| class.contains(c)),
+            CharClass::Intersection(classes) => classes.iter().all(|class| class.contains(c)),
+            CharClass::Difference(a, b) => a.contains(c) && !b.contains(c),
+            #[cfg(feature = "unicode")]
+            CharClass::Category(category) => unicode_general_category::get_general_category(c) == *category,
+            #[cfg(feature = "unicode")]
+            CharClass::Script(script) => unicode_script::UnicodeScript::script(&c) == *script,
+            #[cfg(feature = "unicode")]
+            CharClass::XidStart => unicode_ident::is_xid_start(c),
+            #[cfg(feature = "unicode")]
+            CharClass::XidContinue => unicode_ident::is_xid_continue(c),
+        }
+    }
+
+    pub fn union(self, other : CharClass) -> Self
+    {
+        match self
+        {
+            CharClass::Union(mut classes) =>
+            {
+                classes.push(other);
+                CharClass::Union(classes)
+            },
+            class => CharClass::Union(vec![class, other]),
+        }
+    }
+
+    pub fn intersection(self, other : CharClass) -> Self
+    {
+        match self
+        {
+            CharClass::Intersection(mut classes) =>
+            {
+                classes.push(other);
+                CharClass::Intersection(classes)
+            },
+            class => CharClass::Intersection(vec![class, other]),
+        }
+    }
+
+    pub fn difference(self, other : CharClass) -> Self { CharClass::Difference(Box::new(self), Box::new(other)) }
+
+    pub fn negate(self) -> Self
+    {
+        match self
+        {
+            CharClass::Not(class) => *class,
+            class => CharClass::Not(Box::new(class)),
+        }
+    }
+}
+
+impl BitOr for CharClass
+{
+    type Output = CharClass;
+
+    fn bitor(self, other : CharClass) -> CharClass { self.union(other) }
+}
+
+impl BitAnd for CharClass
+{
+    type Output = CharClass;
+
+    fn bitand(self, other : CharClass) -> CharClass { self.intersection(other) }
+}
+
+impl Sub for CharClass
+{
+    type Output = CharClass;
+
+    fn sub(self, other : CharClass) -> CharClass { self.difference(other) }
+}
+
+impl Not for CharClass
+{
+    type Output = CharClass;
+
+    fn not(self) -> CharClass { self.negate() }
+}
+
+// Reads a single character within the class
+#[inline]
+pub fn char_class<'a>(class : CharClass) -> impl Parser<'a, char> { read_char_f(move |c| class.contains(c)) }
+
+#[inline]
+pub fn char_range<'a>(lo : char, hi : char) -> impl Parser<'a, char> { read_char_f(move |c| (lo..=hi).contains(&c)) }
+
+// Reads a single character that is not within `chars`, the opposite of `char_in_str`
+#[inline]
+pub fn none_of<'a>(chars : &'a str) -> impl Parser<'a, char> { read_char_f(move |c| !chars.contains(c)) }
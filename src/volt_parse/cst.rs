@@ -0,0 +1,340 @@
+use super::{
+    file_pos::FilePos,
+    incremental::reuse,
+    parser::{PErr, POut, PRes, PResData, Parser, ParserInput},
+    span::{consumed, Span},
+};
+use std::{cell::RefCell, fmt::Display};
+
+// A lossless concrete syntax tree, where the text of every token in order reproduces the parsed text exactly
+
+// The kind given to text within a node that no `leaf` or `node` claimed, split into whitespace and everything else
+pub const WHITESPACE_KIND : &str = "whitespace";
+pub const TEXT_KIND : &str = "text";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CstToken
+{
+    pub kind : String,
+    pub text : String,
+    pub span : Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CstNode
+{
+    pub kind :     String,
+    pub span :     Span,
+    pub children : Vec<CstElement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CstElement
+{
+    Node(CstNode),
+    Token(CstToken),
+}
+
+// An element recorded while parsing, positioned by the length of the input remaining at its start and end, as every
+// input within a single parse is a suffix of the same text
+struct Recorded
+{
+    start : usize,
+    end :   usize,
+    elem :  CstElement,
+}
+
+thread_local! {
+    static FRAMES : RefCell<Vec<Vec<Recorded>>> = const { RefCell::new(vec![]) };
+}
+
+pub(super) fn record(start : usize, end : usize, elem : CstElement)
+{
+    FRAMES.with(|frames| {
+        if let Some(frame) = frames.borrow_mut().last_mut()
+        {
+            frame.push(Recorded {
+                start,
+                end,
+                elem,
+            })
+        }
+    });
+}
+
+// How many elements the innermost `node` has recorded so far, or `None` outside of any node
+fn checkpoint() -> Option<usize> { FRAMES.with(|frames| frames.borrow().last().map(Vec::len)) }
+
+// Discards whatever the innermost `node` recorded since the checkpoint
+fn rollback(mark : Option<usize>)
+{
+    if let Some(mark) = mark
+    {
+        FRAMES.with(|frames| {
+            if let Some(frame) = frames.borrow_mut().last_mut()
+            {
+                frame.truncate(mark)
+            }
+        });
+    }
+}
+
+// Runs `f`, discarding anything that `leaf` and `node` recorded within it should it fail. Any parser that carries on
+// after another has failed, such as `or`, tries it through this so that the failed attempt leaves nothing in the tree.
+pub fn attempt<T>(f : impl FnOnce() -> Result<T, PErr>) -> Result<T, PErr>
+{
+    let mark = checkpoint();
+    let res = f();
+    if res.is_err()
+    {
+        rollback(mark);
+    }
+    res
+}
+
+// Runs `f`, always discarding anything that `leaf` and `node` recorded within it, for looking ahead without consuming
+pub fn lookahead<T>(f : impl FnOnce() -> Result<T, PErr>) -> Result<T, PErr>
+{
+    let mark = checkpoint();
+    let res = f();
+    rollback(mark);
+    res
+}
+
+// Records the text matched by `p` as a single token of the given kind within the enclosing `node`
+#[inline]
+pub fn leaf<'a, DatT : PResData>(kind : &'a str, p : impl Parser<'a, DatT>) -> impl Parser<'a, CstToken>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, CstToken> {
+        let res = p(ind)?;
+        let token = CstToken {
+            kind : kind.to_string(),
+            text : consumed(ind.text, res.remainder).to_string(),
+            span : Span::new(ind.pos, res.pos),
+        };
+        record(ind.text.len(), res.remainder.len(), CstElement::Token(token.clone()));
+        Ok(res.with_val(token))
+    }
+}
+
+// Builds a node of the given kind out of the text matched by `p`, regardless of the value that `p` produces.
+//
+// Any `leaf` or `node` matched within `p` becomes a child, and the remaining text becomes whitespace and text tokens.
+// Whatever a failed alternative recorded is discarded, so only what the successful parse matched is kept, provided
+// hand written parsers that carry on after a failure try it through `attempt`. Empty nodes
+// and tokens are left out, as they do not contribute any text. When reparsing through an `IncrementalParser`, a node
+// left unaffected by the edit is reused instead of running `p` again.
+#[inline]
+pub fn node<'a, DatT : PResData>(kind : &'a str, p : impl Parser<'a, DatT>) -> impl Parser<'a, CstNode>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, CstNode> {
+        if let Some(node) = reuse(kind, ind)
+        {
+            let len = node.len();
+            record(ind.text.len(), ind.text.len() - len, CstElement::Node(node.clone()));
+            return Ok(PRes {
+                pos :       node.span.end,
+                val :       node,
+                remainder : &ind.text[len..],
+            });
+        }
+
+        FRAMES.with(|frames| frames.borrow_mut().push(vec![]));
+        let res = p(ind);
+        let recorded = FRAMES.with(|frames| frames.borrow_mut().pop()).unwrap_or_default();
+        let res = res?;
+
+        let node = CstNode {
+            kind :     kind.to_string(),
+            span :     Span::new(ind.pos, res.pos),
+            children : arrange(ind, res.remainder.len(), recorded),
+        };
+        record(ind.text.len(), res.remainder.len(), CstElement::Node(node.clone()));
+        Ok(res.with_val(node))
+    }
+}
+
+// Lays the recorded elements out end to end, filling any gaps with tokens
+fn arrange(ind : &ParserInput, end : usize, recorded : Vec<Recorded>) -> Vec<CstElement>
+{
+    let mut children = vec![];
+    let mut cursor = ind.text.len();
+    let mut pos = ind.pos;
+    while cursor > end
+    {
+        let found = recorded
+            .iter()
+            .rev()
+            .find(|r| r.start == cursor && r.end < r.start && r.end >= end);
+        match found
+        {
+            Some(r) =>
+            {
+                pos = r.elem.span().end;
+                cursor = r.end;
+                children.push(r.elem.clone());
+            },
+            None =>
+            {
+                let gap_end = recorded
+                    .iter()
+                    .filter(|r| r.start < cursor && r.end < r.start)
+                    .map(|r| r.start)
+                    .max()
+                    .unwrap_or(end)
+                    .max(end);
+                let text = &ind.text[ind.text.len() - cursor..ind.text.len() - gap_end];
+                pos = gap_tokens(text, pos, &mut children);
+                cursor = gap_end;
+            },
+        }
+    }
+    children
+}
+
+fn gap_tokens(text : &str, mut pos : FilePos, out : &mut Vec<CstElement>) -> FilePos
+{
+    let mut rest = text;
+    while let Some(first) = rest.chars().next()
+    {
+        let ws = first.is_whitespace();
+        let len = rest.find(|c : char| c.is_whitespace() != ws).unwrap_or(rest.len());
+        let end = pos.advance(&rest[..len]);
+        out.push(CstElement::Token(CstToken {
+            kind : String::from(if ws { WHITESPACE_KIND } else { TEXT_KIND }),
+            text : rest[..len].to_string(),
+            span : Span::new(pos, end),
+        }));
+        pos = end;
+        rest = &rest[len..];
+    }
+    pos
+}
+
+impl CstElement
+{
+    pub fn span(&self) -> Span
+    {
+        match self
+        {
+            CstElement::Node(n) => n.span,
+            CstElement::Token(t) => t.span,
+        }
+    }
+
+    pub fn kind(&self) -> &str
+    {
+        match self
+        {
+            CstElement::Node(n) => &n.kind,
+            CstElement::Token(t) => &t.kind,
+        }
+    }
+
+    // The length of the text within this element, in bytes
+    pub fn len(&self) -> usize
+    {
+        match self
+        {
+            CstElement::Node(n) => n.len(),
+            CstElement::Token(t) => t.text.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    fn write_text(&self, out : &mut String)
+    {
+        match self
+        {
+            CstElement::Node(n) => n.children.iter().for_each(|c| c.write_text(out)),
+            CstElement::Token(t) => out.push_str(&t.text),
+        }
+    }
+}
+
+impl CstNode
+{
+    // Reproduces the exact text that this node was parsed from
+    pub fn text(&self) -> String
+    {
+        let mut out = String::new();
+        self.children.iter().for_each(|c| c.write_text(&mut out));
+        out
+    }
+
+    // The length of the text within this node, in bytes
+    pub fn len(&self) -> usize { self.children.iter().map(|c| c.len()).sum() }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    // Every token within this node, in order
+    pub fn tokens(&self) -> Vec<&CstToken>
+    {
+        let mut out = vec![];
+        for child in &self.children
+        {
+            match child
+            {
+                CstElement::Node(n) => out.extend(n.tokens()),
+                CstElement::Token(t) => out.push(t),
+            }
+        }
+        out
+    }
+
+    // The direct child nodes of this node
+    pub fn nodes(&self) -> impl Iterator<Item = &CstNode>
+    {
+        self.children.iter().filter_map(|c| match c
+        {
+            CstElement::Node(n) => Some(n),
+            CstElement::Token(_) => None,
+        })
+    }
+
+    // An indented outline of the tree, useful for debugging
+    pub fn dump(&self) -> String
+    {
+        let mut out = String::new();
+        self.dump_into(0, &mut out);
+        out
+    }
+
+    fn dump_into(&self, depth : usize, out : &mut String)
+    {
+        out.push_str(&format!(
+            "{}{} {}\n",
+            "  ".repeat(depth),
+            self.kind,
+            fmt_span(self.span)
+        ));
+        for child in &self.children
+        {
+            match child
+            {
+                CstElement::Node(n) => n.dump_into(depth + 1, out),
+                CstElement::Token(t) => out.push_str(&format!(
+                    "{}{} {} {:?}\n",
+                    "  ".repeat(depth + 1),
+                    t.kind,
+                    fmt_span(t.span),
+                    t.text
+                )),
+            }
+        }
+    }
+}
+
+fn fmt_span(span : Span) -> String
+{
+    format!(
+        "{}:{}..{}:{}",
+        span.start.line, span.start.column, span.end.line, span.end.column
+    )
+}
+
+impl Display for CstNode
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str(&self.text()) }
+}
@@ -0,0 +1,7 @@
+use super::parser::{PResData, Parser};
+
+// A type that knows how to parse itself, usually implemented with `#[derive(VoltParse)]` from the `macros` feature
+pub trait VoltParse: PResData
+{
+    fn parser<'a>() -> impl Parser<'a, Self>;
+}
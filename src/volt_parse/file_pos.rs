@@ -1,4 +1,4 @@
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FilePos
 {
     pub line :   usize,
@@ -30,4 +30,19 @@ impl FilePos
             column : 0,
         }
     }
+
+    // The position after reading `text` from this position, moving onto the next line for each '\n'
+    pub fn advance(&self, text : &str) -> Self
+    {
+        text.chars().fold(*self, |pos, c| {
+            if c == '\n'
+            {
+                pos.incr_line()
+            }
+            else
+            {
+                pos.incr_col()
+            }
+        })
+    }
 }
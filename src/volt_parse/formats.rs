@@ -0,0 +1,7 @@
+// Parsers for common file formats, built from the crate's own parsers
+pub mod csv;
+pub mod datetime;
+pub mod ini;
+pub mod json;
+pub mod toml;
+pub mod uri;
@@ -0,0 +1,263 @@
+use crate::prelude::*;
+
+// How the fields and records of a CSV file are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvFormat
+{
+    pub delimiter : char,
+    // Surrounds fields that contain delimiters, quotes or newlines, or `None` if fields are never quoted
+    pub quote :     Option<char>,
+    // Escapes a quote within a quoted field, or `None` if quotes are escaped by doubling them as in RFC 4180
+    pub escape :    Option<char>,
+    // Whether the first record names the columns, rather than being data
+    pub header :    bool,
+}
+
+impl Default for CsvFormat
+{
+    fn default() -> Self
+    {
+        Self {
+            delimiter : ',',
+            quote :     Some('"'),
+            escape :    None,
+            header :    false,
+        }
+    }
+}
+
+impl CsvFormat
+{
+    // Tab separated values
+    pub fn tsv() -> Self
+    {
+        Self {
+            delimiter : '\t',
+            ..Self::default()
+        }
+    }
+
+    pub fn with_delimiter(mut self, delimiter : char) -> Self
+    {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn with_quote(mut self, quote : Option<char>) -> Self
+    {
+        self.quote = quote;
+        self
+    }
+
+    pub fn with_escape(mut self, escape : char) -> Self
+    {
+        self.escape = Some(escape);
+        self
+    }
+
+    pub fn with_header(mut self) -> Self
+    {
+        self.header = true;
+        self
+    }
+}
+
+// The fields of a single record, with quotes and escapes removed
+pub type CsvRecord = Vec<Spanned<String>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Csv
+{
+    pub headers : Option<CsvRecord>,
+    pub records : Vec<CsvRecord>,
+}
+
+impl Csv
+{
+    // The index of the column with the given header
+    pub fn column(&self, name : &str) -> Option<usize> { self.headers.as_ref()?.iter().position(|h| h.val == name) }
+}
+
+// Parses every record of a CSV file, failing at the first field that is not well formed
+pub fn parse_csv(text : &str, format : CsvFormat) -> Result<Csv, PErr>
+{
+    let mut reader = CsvReader::new(text, format)?;
+    let records = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+    Ok(Csv {
+        headers : reader.headers,
+        records,
+    })
+}
+
+// Reads the records of a CSV file one at a time, stopping after the first error. Blank lines hold no record and are
+// skipped, rather than read as a record with a single empty field.
+#[derive(Debug, Clone)]
+pub struct CsvReader<'a>
+{
+    input :   ParserInput<'a>,
+    format :  CsvFormat,
+    headers : Option<CsvRecord>,
+    failed :  bool,
+}
+
+impl<'a> CsvReader<'a>
+{
+    // Reads the header record straight away if the format has one
+    pub fn new(text : &'a str, format : CsvFormat) -> Result<Self, PErr>
+    {
+        let mut reader = Self {
+            input : ParserInput::new(text),
+            format,
+            headers : None,
+            failed : false,
+        };
+        if format.header
+        {
+            reader.headers = reader.next().transpose()?;
+        }
+        Ok(reader)
+    }
+
+    pub fn headers(&self) -> Option<&CsvRecord> { self.headers.as_ref() }
+}
+
+impl<'a> Iterator for CsvReader<'a>
+{
+    type Item = Result<CsvRecord, PErr>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if self.failed
+        {
+            return None;
+        }
+        while let Ok(res) = newline()(&self.input)
+        {
+            self.input = res.to_in();
+        }
+        if self.input.text.is_empty()
+        {
+            return None;
+        }
+        match then(csv_record(self.format), line_end(), left_right)(&self.input)
+        {
+            Ok(res) =>
+            {
+                self.input = res.to_in();
+                Some(Ok(res.val.0))
+            },
+            Err(err) =>
+            {
+                self.failed = true;
+                Some(Err(err))
+            },
+        }
+    }
+}
+
+// A newline, or the end of the input. A lone `\r` is not a line end, since positions only move onto the next line at
+// a `\n`.
+fn line_end<'a>() -> impl Parser<'a, String>
+{
+    or(newline(), succeed_if(always(String::new), |r| r.remainder.is_empty()))
+}
+
+// A single record, up to but not including the line end that follows it
+#[inline]
+pub fn csv_record<'a>(format : CsvFormat) -> impl Parser<'a, CsvRecord>
+{
+    let field = csv_field(format);
+    move |ind : &ParserInput<'a>| -> POut<'a, CsvRecord> {
+        let mut fields = vec![];
+        let mut cur = *ind;
+        loop
+        {
+            let res = field(&cur)?;
+            let after = res.to_in();
+            fields.push(res.val);
+            match char_single(format.delimiter)(&after)
+            {
+                Ok(delim) => cur = delim.to_in(),
+                Err(_) =>
+                    return Ok(PRes {
+                        val :       fields,
+                        pos :       after.pos,
+                        remainder : after.text,
+                    }),
+            }
+        }
+    }
+}
+
+// A single field, which ends at a delimiter, a newline or the end of the input. Quoted fields may contain any of
+// these, and fail at their opening quote if they are never closed or at whatever follows the closing quote if it does
+// not end the field. An unquoted `\r` also ends a field, so that a lone one is rejected by the record rather than kept
+// within the field.
+#[inline]
+pub fn csv_field<'a>(format : CsvFormat) -> impl Parser<'a, Spanned<String>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Spanned<String>> {
+        let text = ind.text;
+        let ends_field =
+            |rest : &str| rest.is_empty() || rest.starts_with(format.delimiter) || rest.starts_with(['\n', '\r']);
+        let done = |val : String, end : usize| {
+            let pos = ind.pos.advance(&text[..end]);
+            Ok(PRes {
+                val : Spanned {
+                    val,
+                    span : Span::new(ind.pos, pos),
+                },
+                pos,
+                remainder : &text[end..],
+            })
+        };
+
+        let quote = match format.quote
+        {
+            Some(q) if text.starts_with(q) => q,
+            _ =>
+            {
+                let end = text
+                    .char_indices()
+                    .find(|(i, _)| ends_field(&text[*i..]))
+                    .map_or(text.len(), |(i, _)| i);
+                return done(String::from(&text[..end]), end);
+            },
+        };
+
+        let mut val = String::new();
+        let mut chars = text.char_indices().skip(1).peekable();
+        while let Some((i, c)) = chars.next()
+        {
+            let next = chars.peek().map(|(_, n)| *n);
+            if Some(c) == format.escape && (next == Some(quote) || next == format.escape)
+            {
+                val.extend(next);
+                chars.next();
+            }
+            else if c == quote && format.escape.is_none() && next == Some(quote)
+            {
+                val.push(quote);
+                chars.next();
+            }
+            else if c == quote
+            {
+                let end = i + c.len_utf8();
+                if !ends_field(&text[end..])
+                {
+                    return Err(PErr {
+                        pos : ind.pos.advance(&text[..end]),
+                    });
+                }
+                return done(val, end);
+            }
+            else
+            {
+                val.push(c);
+            }
+        }
+        Err(PErr {
+            pos : ind.pos
+        })
+    }
+}
@@ -0,0 +1,794 @@
+use crate::prelude::*;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date
+{
+    pub year :  u16,
+    pub month : u8,
+    pub day :   u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time
+{
+    pub hour :       u8,
+    pub minute :     u8,
+    // Up to 60, to allow for leap seconds
+    pub second :     u8,
+    pub nanosecond : u32,
+}
+
+// An offset from UTC, where `Z` is kept apart from `+00:00`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Offset
+{
+    Utc,
+    Minutes(i16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime
+{
+    pub date :   Date,
+    pub time :   Time,
+    // `None` for a local date and time
+    pub offset : Option<Offset>,
+}
+
+// An ISO 8601 duration such as `P1Y2M10DT2H30M`, which is kept as written since the length of its years, months and
+// days depends on when it begins
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Duration
+{
+    pub years :       u32,
+    pub months :      u32,
+    pub weeks :       u32,
+    pub days :        u32,
+    pub hours :       u32,
+    pub minutes :     u32,
+    pub seconds :     u32,
+    // The fraction of the seconds, which is the only component that may have one
+    pub nanoseconds : u32,
+}
+
+pub fn is_leap_year(year : u16) -> bool
+{
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+pub fn days_in_month(year : u16, month : u8) -> u8
+{
+    match month
+    {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+pub fn days_in_year(year : u16) -> u16
+{
+    if is_leap_year(year)
+    {
+        366
+    }
+    else
+    {
+        365
+    }
+}
+
+// The number of ISO 8601 weeks in the year, which has 53 when it begins on a Thursday, or on a Wednesday in a leap year
+pub fn weeks_in_year(year : u16) -> u8
+{
+    let first = Date {
+        year,
+        month : 1,
+        day : 1,
+    }
+    .weekday();
+    if first == 4 || (first == 3 && is_leap_year(year))
+    {
+        53
+    }
+    else
+    {
+        52
+    }
+}
+
+// The number of days from 1970-01-01 to the date
+fn days_since_epoch(date : &Date) -> i64
+{
+    let (month, day) = (i64::from(date.month), i64::from(date.day));
+    let year = i64::from(date.year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+impl Date
+{
+    // The date that is the given day of the year, counting from 1
+    pub fn from_ordinal(year : u16, ordinal : u16) -> Option<Date>
+    {
+        if ordinal == 0 || ordinal > days_in_year(year)
+        {
+            return None;
+        }
+        let mut day = ordinal;
+        let mut month = 1;
+        while day > u16::from(days_in_month(year, month))
+        {
+            day -= u16::from(days_in_month(year, month));
+            month += 1;
+        }
+        Some(Date {
+            year,
+            month,
+            day : day as u8,
+        })
+    }
+
+    // The date of an ISO 8601 week date, where week 1 is the week with the year's first Thursday, and the weekday
+    // counts from 1 for Monday. `None` if the week is not within the year, or the date would be outside years 0-9999.
+    pub fn from_week_date(year : u16, week : u8, weekday : u8) -> Option<Date>
+    {
+        if week == 0 || week > weeks_in_year(year) || !(1..=7).contains(&weekday)
+        {
+            return None;
+        }
+        let jan4 = Date {
+            year,
+            month : 1,
+            day : 4,
+        }
+        .weekday();
+        let ordinal = i32::from(week) * 7 + i32::from(weekday) - (i32::from(jan4) + 3);
+        if ordinal < 1
+        {
+            let previous = year.checked_sub(1)?;
+            Date::from_ordinal(previous, (ordinal + i32::from(days_in_year(previous))) as u16)
+        }
+        else if ordinal > i32::from(days_in_year(year))
+        {
+            let next = year.checked_add(1).filter(|y| *y <= 9999)?;
+            Date::from_ordinal(next, (ordinal - i32::from(days_in_year(year))) as u16)
+        }
+        else
+        {
+            Date::from_ordinal(year, ordinal as u16)
+        }
+    }
+
+    // The day of the year, counting from 1
+    pub fn ordinal(&self) -> u16
+    {
+        (1..self.month)
+            .map(|m| u16::from(days_in_month(self.year, m)))
+            .sum::<u16>() +
+            u16::from(self.day)
+    }
+
+    // The day of the week, from 1 for Monday to 7 for Sunday
+    pub fn weekday(&self) -> u8 { ((days_since_epoch(self) + 3).rem_euclid(7) + 1) as u8 }
+}
+
+impl DateTime
+{
+    // The number of seconds since 1970-01-01T00:00:00Z, or `None` for a local date and time
+    pub fn unix_timestamp(&self) -> Option<i64>
+    {
+        let offset = match self.offset?
+        {
+            Offset::Utc => 0,
+            Offset::Minutes(m) => i64::from(m),
+        };
+        let time = i64::from(self.time.hour) * 3600 + i64::from(self.time.minute) * 60 + i64::from(self.time.second);
+        Some(days_since_epoch(&self.date) * 86400 + time - offset * 60)
+    }
+}
+
+impl Display for Date
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Display for Time
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nanosecond > 0
+        {
+            write!(f, ".{}", format!("{:09}", self.nanosecond).trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Offset
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Offset::Utc => write!(f, "Z"),
+            Offset::Minutes(m) =>
+            {
+                let sign = if *m < 0 { '-' } else { '+' };
+                write!(f, "{sign}{:02}:{:02}", m.abs() / 60, m.abs() % 60)
+            },
+        }
+    }
+}
+
+impl Display for Duration
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "P")?;
+        for (n, unit) in [
+            (self.years, 'Y'),
+            (self.months, 'M'),
+            (self.weeks, 'W'),
+            (self.days, 'D'),
+        ]
+        {
+            if n > 0
+            {
+                write!(f, "{n}{unit}")?;
+            }
+        }
+        let seconds = self.seconds > 0 || self.nanoseconds > 0;
+        if self.hours > 0 || self.minutes > 0 || seconds || *self == Duration::default()
+        {
+            write!(f, "T")?;
+        }
+        for (n, unit) in [(self.hours, 'H'), (self.minutes, 'M')]
+        {
+            if n > 0
+            {
+                write!(f, "{n}{unit}")?;
+            }
+        }
+        if seconds || *self == Duration::default()
+        {
+            write!(f, "{}", self.seconds)?;
+            if self.nanoseconds > 0
+            {
+                write!(f, ".{}", format!("{:09}", self.nanoseconds).trim_end_matches('0'))?;
+            }
+            write!(f, "S")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DateTime
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}T{}", self.date, self.time)?;
+        match self.offset
+        {
+            Some(offset) => write!(f, "{offset}"),
+            None => Ok(()),
+        }
+    }
+}
+
+// A date as `YYYY-MM-DD`, failing at the first field that is out of range, including days past the end of the month
+#[inline]
+pub fn date<'a>() -> impl Parser<'a, Date>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Date> {
+        let year = fixed(4, 0, 9999)(ind)?;
+        let month = then(char_single('-'), fixed(2, 1, 12), take_right)(&year.to_in())?;
+        let dash = char_single('-')(&month.to_in())?;
+        let day = fixed(2, 1, days_in_month(year.val as u16, month.val as u8) as u32)(&dash.to_in())?;
+        Ok(day.with_val(Date {
+            year :  year.val as u16,
+            month : month.val as u8,
+            day :   day.val as u8,
+        }))
+    }
+}
+
+// A time as `HH:MM:SS`, with an optional fraction of a second that is truncated to nanoseconds
+#[inline]
+pub fn time<'a>() -> impl Parser<'a, Time>
+{
+    let colon = |max| then(char_single(':'), fixed(2, 0, max), take_right);
+    let fraction = then(char_single('.'), one_or_many(ascii_digit()), take_right);
+    mod_val(
+        then(
+            fixed(2, 0, 23),
+            then(colon(59), then(colon(60), maybe(fraction), left_right), left_right),
+            left_right,
+        ),
+        |(hour, (minute, (second, fraction)))| Time {
+            hour :       hour as u8,
+            minute :     minute as u8,
+            second :     second as u8,
+            nanosecond : nanoseconds(&fraction.unwrap_or_default()),
+        },
+    )
+}
+
+// `Z`, or an offset of hours and minutes such as `+05:30`
+#[inline]
+pub fn offset<'a>() -> impl Parser<'a, Offset>
+{
+    let utc = mod_val(char_in_str("Zz"), |_| Offset::Utc);
+    let minutes = mod_val(
+        then(
+            char_in_str("+-"),
+            then(
+                fixed(2, 0, 23),
+                then(char_single(':'), fixed(2, 0, 59), take_right),
+                left_right,
+            ),
+            left_right,
+        ),
+        |(sign, (h, m))| {
+            let minutes = (h * 60 + m) as i16;
+            Offset::Minutes(if sign == '-' { -minutes } else { minutes })
+        },
+    );
+    or(utc, minutes)
+}
+
+// A date and time separated by `T` or a space, without the offset that may follow
+fn local_date_time<'a>() -> impl Parser<'a, (Date, Time)>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, (Date, Time)> {
+        let date = date()(ind)?;
+        let sep = char_in_str("Tt ")(&date.to_in())?;
+        let time = time()(&sep.to_in())?;
+        Ok(time.with_val((date.val, time.val)))
+    }
+}
+
+// A date and time as in RFC 3339, separated by `T` or a space, where the offset is optional so that local date times
+// can also be read, see `rfc3339` to require it
+#[inline]
+pub fn date_time<'a>() -> impl Parser<'a, DateTime>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, DateTime> {
+        let local = local_date_time()(ind)?;
+        let offset = maybe(offset())(&local.to_in())?;
+        Ok(offset.with_val(DateTime {
+            date :   local.val.0,
+            time :   local.val.1,
+            offset : offset.val,
+        }))
+    }
+}
+
+// A date and time as in RFC 3339 with its offset, failing where the offset should be if it is missing or out of range
+#[inline]
+pub fn rfc3339<'a>() -> impl Parser<'a, DateTime>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, DateTime> {
+        let local = local_date_time()(ind)?;
+        let offset = offset()(&local.to_in())?;
+        Ok(offset.with_val(DateTime {
+            date :   local.val.0,
+            time :   local.val.1,
+            offset : Some(offset.val),
+        }))
+    }
+}
+
+// A date in any of the forms of ISO 8601, being calendar dates such as `2024-03-15`, ordinal dates such as `2024-075`
+// and week dates such as `2024-W11-5`, each either with hyphens or in the basic form without them, such as `20240315`
+//
+// Fails at the first field that is out of range, such as week 53 of a year with 52 weeks or day 366 of a year that is
+// not a leap year.
+#[inline]
+pub fn iso8601_date<'a>() -> impl Parser<'a, Date>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Date> {
+        let year = fixed(4, 0, 9999)(ind)?;
+        let y = year.val as u16;
+        let extended = year.remainder.starts_with('-');
+        // The hyphen between fields, which must be used for all of them or none
+        let sep = move |res : &ParserInput<'a>| -> POut<'a, ()> {
+            match extended
+            {
+                true => mod_val(char_single('-'), |_| ())(res),
+                false => Ok(PRes {
+                    val :       (),
+                    pos :       res.pos,
+                    remainder : res.text,
+                }),
+            }
+        };
+        let body = sep(&year.to_in())?.to_in();
+
+        if body.text.starts_with('W')
+        {
+            let week = then(char_single('W'), fixed(2, 1, u32::from(weeks_in_year(y))), take_right)(&body)?;
+            let weekday = then(sep, fixed(1, 1, 7), take_right)(&week.to_in())?;
+            let date = Date::from_week_date(y, week.val as u8, weekday.val as u8).ok_or(PErr {
+                pos : ind.pos
+            })?;
+            return Ok(weekday.with_val(date));
+        }
+
+        let digits = body.text.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 3 && !body.text[3..].starts_with('-')
+        {
+            let ordinal = fixed(3, 1, u32::from(days_in_year(y)))(&body)?;
+            let date = Date::from_ordinal(y, ordinal.val as u16).ok_or(PErr {
+                pos : body.pos
+            })?;
+            return Ok(ordinal.with_val(date));
+        }
+
+        let month = fixed(2, 1, 12)(&body)?;
+        let day = then(
+            sep,
+            fixed(2, 1, u32::from(days_in_month(y, month.val as u8))),
+            take_right,
+        )(&month.to_in())?;
+        Ok(day.with_val(Date {
+            year :  y,
+            month : month.val as u8,
+            day :   day.val as u8,
+        }))
+    }
+}
+
+// A time of day as in ISO 8601, being hours and minutes with optional seconds, either with colons such as `14:30:15`
+// or in the basic form without them such as `143015`, where the seconds may have a fraction after a `.` or `,`
+#[inline]
+pub fn iso8601_time<'a>() -> impl Parser<'a, Time>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Time> {
+        let hour = fixed(2, 0, 23)(ind)?;
+        let extended = hour.remainder.starts_with(':');
+        let field = move |max| {
+            let digits = fixed(2, 0, max);
+            move |res : &ParserInput<'a>| -> POut<'a, u32> {
+                match extended
+                {
+                    true => then(char_single(':'), digits.clone(), take_right)(res),
+                    false => digits(res),
+                }
+            }
+        };
+        let minute = field(59)(&hour.to_in())?;
+        let second = maybe(field(60))(&minute.to_in())?;
+        let fraction = match second.val
+        {
+            Some(_) => maybe(then(char_in_str(".,"), one_or_many(ascii_digit()), take_right))(&second.to_in())?,
+            None => second.with_val(None),
+        };
+        Ok(fraction.with_val(Time {
+            hour :       hour.val as u8,
+            minute :     minute.val as u8,
+            second :     second.val.unwrap_or(0) as u8,
+            nanosecond : nanoseconds(&fraction.val.clone().unwrap_or_default()),
+        }))
+    }
+}
+
+// `Z`, or an offset of hours with optional minutes, such as `+05:30`, `+0530` or `+05`
+#[inline]
+pub fn iso8601_offset<'a>() -> impl Parser<'a, Offset>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Offset> {
+        if let Ok(utc) = char_single('Z')(ind)
+        {
+            return Ok(utc.with_val(Offset::Utc));
+        }
+        let sign = char_in_str("+-")(ind)?;
+        let hours = fixed(2, 0, 23)(&sign.to_in())?;
+        let minutes = maybe(then(maybe(char_single(':')), fixed(2, 0, 59), take_right))(&hours.to_in())?;
+        let total = (hours.val * 60 + minutes.val.unwrap_or(0)) as i16;
+        Ok(minutes.with_val(Offset::Minutes(if sign.val == '-' { -total } else { total })))
+    }
+}
+
+// A date and time as in ISO 8601 separated by `T`, such as `2024-W11-5T14:30Z` or `20240315T143000+0100`, where the
+// offset is optional so that local date times can also be read
+#[inline]
+pub fn iso8601<'a>() -> impl Parser<'a, DateTime>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, DateTime> {
+        let date = iso8601_date()(ind)?;
+        let time = then(char_single('T'), iso8601_time(), take_right)(&date.to_in())?;
+        let offset = maybe(iso8601_offset())(&time.to_in())?;
+        Ok(offset.with_val(DateTime {
+            date :   date.val,
+            time :   time.val,
+            offset : offset.val,
+        }))
+    }
+}
+
+// An ISO 8601 duration, such as `P3Y6M4DT12H30M5S`, `P2W` or `PT0.5S`, whose components must be in order and of which
+// there must be at least one, including at least one after a `T`
+//
+// Fails at a designator that is out of order or unknown, at a number too large for its component, or just after the
+// `P` or `T` that nothing follows.
+#[inline]
+pub fn iso8601_duration<'a>() -> impl Parser<'a, Duration>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Duration> {
+        let mut duration = Duration::default();
+        let mut cur = char_single('P')(ind)?.to_in();
+        let mut time = false;
+        let mut units = "YMWD";
+        let mut empty = true;
+        loop
+        {
+            if !time && cur.text.starts_with('T')
+            {
+                cur = char_single('T')(&cur)?.to_in();
+                time = true;
+                units = "HMS";
+                empty = true;
+                continue;
+            }
+            if !cur.text.starts_with(|c : char| c.is_ascii_digit())
+            {
+                break;
+            }
+
+            let number = one_or_many(ascii_digit())(&cur)?;
+            let value = number.val.iter().collect::<String>().parse::<u32>().map_err(|_| PErr {
+                pos : cur.pos
+            })?;
+            let mut after = number.to_in();
+            let mut fraction = None;
+            if units.contains('S')
+            {
+                if let Ok(res) = then(char_in_str(".,"), one_or_many(ascii_digit()), take_right)(&after)
+                {
+                    after = res.to_in();
+                    fraction = Some(res.val);
+                }
+            }
+            let unit = read_char_f(|c| c.is_ascii_uppercase())(&after)
+                .ok()
+                .filter(|u| units.contains(u.val) && (fraction.is_none() || u.val == 'S'))
+                .ok_or(PErr {
+                    pos : after.pos
+                })?;
+            match (time, unit.val)
+            {
+                (false, 'Y') => duration.years = value,
+                (false, 'M') => duration.months = value,
+                (false, 'W') => duration.weeks = value,
+                (false, _) => duration.days = value,
+                (true, 'H') => duration.hours = value,
+                (true, 'M') => duration.minutes = value,
+                (true, _) =>
+                {
+                    duration.seconds = value;
+                    duration.nanoseconds = nanoseconds(&fraction.unwrap_or_default());
+                },
+            }
+            units = &units[units.find(unit.val).unwrap_or(0) + 1..];
+            empty = false;
+            cur = unit.to_in();
+        }
+        // Either the `P` or the `T` has nothing after it
+        if empty
+        {
+            return Err(PErr {
+                pos : cur.pos
+            });
+        }
+        Ok(PRes {
+            val :       duration,
+            pos :       cur.pos,
+            remainder : cur.text,
+        })
+    }
+}
+
+const DAY_NAMES : [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTH_NAMES : [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// A date and time as in an email header of RFC 2822, such as `Fri, 21 Nov 1997 09:55:06 -0600`, along with the
+// obsolete forms it still allows, being two or three digit years, zone names such as `GMT` and `EST`, and comments
+// within parentheses where whitespace may be
+//
+// Fails at the day of the week if it is not the day of the date, at a day past the end of the month, or otherwise at
+// the first field that is out of range or not well formed.
+#[inline]
+pub fn rfc2822<'a>() -> impl Parser<'a, DateTime>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, DateTime> {
+        let space = trivia(Trivia::whitespace().with_block_comment("(", ")").with_nested_comments());
+        let skip = |res : &ParserInput<'a>| space(res).map(|r| r.to_in());
+        let name_of = |names : &'static [&'static str]| {
+            move |res : &ParserInput<'a>| -> POut<'a, usize> {
+                let found = names
+                    .iter()
+                    .position(|n| res.text.get(..3).is_some_and(|t| t.eq_ignore_ascii_case(n)))
+                    .ok_or(PErr {
+                        pos : res.pos
+                    })?;
+                let rest = &res.text[3..];
+                Ok(PRes {
+                    val :       found,
+                    pos :       res.pos.advance(&res.text[..3]),
+                    remainder : rest,
+                })
+            }
+        };
+
+        let mut cur = skip(ind)?;
+        let weekday = match cur.text.starts_with(|c : char| c.is_ascii_alphabetic())
+        {
+            true =>
+            {
+                let start = cur.pos;
+                let name = name_of(&DAY_NAMES)(&cur)?;
+                cur = char_single(',')(&skip(&name.to_in())?)?.to_in();
+                Some((name.val as u8 + 1, start))
+            },
+            false => None,
+        };
+
+        let day_start = skip(&cur)?;
+        let day = up_to(2)(&day_start)?;
+        let month = name_of(&MONTH_NAMES)(&skip(&day.to_in())?)?;
+        let year_start = skip(&month.to_in())?;
+        let year = up_to(4)(&year_start)?;
+        let full_year = match year.val.1
+        {
+            2 if year.val.0 < 50 => year.val.0 + 2000,
+            2 | 3 => year.val.0 + 1900,
+            _ => year.val.0,
+        };
+        if year.val.1 < 2 || year.remainder.starts_with(|c : char| c.is_ascii_digit())
+        {
+            return Err(PErr {
+                pos : year_start.pos
+            });
+        }
+
+        let hour = fixed(2, 0, 23)(&skip(&year.to_in())?)?;
+        let colon = |max| {
+            move |res : &ParserInput<'a>| -> POut<'a, u32> {
+                let colon = char_single(':')(&skip(res)?)?;
+                fixed(2, 0, max)(&skip(&colon.to_in())?)
+            }
+        };
+        let minute = colon(59)(&hour.to_in())?;
+        let second = maybe(colon(60))(&minute.to_in())?;
+        let zone = zone()(&skip(&second.to_in())?)?;
+        let end = space(&zone.to_in())?;
+
+        let date = Date {
+            year :  full_year as u16,
+            month : month.val as u8 + 1,
+            day :   day.val.0 as u8,
+        };
+        if date.day == 0 || date.day > days_in_month(date.year, date.month)
+        {
+            return Err(PErr {
+                pos : day_start.pos
+            });
+        }
+        if let Some((weekday, pos)) = weekday
+        {
+            if weekday != date.weekday()
+            {
+                return Err(PErr {
+                    pos,
+                });
+            }
+        }
+        Ok(end.with_val(DateTime {
+            date,
+            time : Time {
+                hour :       hour.val as u8,
+                minute :     minute.val as u8,
+                second :     second.val.unwrap_or(0) as u8,
+                nanosecond : 0,
+            },
+            offset : Some(zone.val),
+        }))
+    }
+}
+
+// One to `n` digits, along with how many there were
+fn up_to<'a>(n : usize) -> impl Parser<'a, (u32, usize)>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, (u32, usize)> {
+        let len = ind.text.bytes().take(n).take_while(u8::is_ascii_digit).count();
+        let digits = &ind.text[..len];
+        let val = digits.parse().map_err(|_| PErr {
+            pos : ind.pos
+        })?;
+        Ok(PRes {
+            val :       (val, len),
+            pos :       ind.pos.advance(digits),
+            remainder : &ind.text[len..],
+        })
+    }
+}
+
+// An offset as `+HHMM` or `-HHMM`, or one of the obsolete zone names, where military zones are taken as UTC as
+// RFC 2822 advises
+fn zone<'a>() -> impl Parser<'a, Offset>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Offset> {
+        if let Ok(sign) = char_in_str("+-")(ind)
+        {
+            let hours = fixed(2, 0, 99)(&sign.to_in())?;
+            let minutes = fixed(2, 0, 59)(&hours.to_in())?;
+            let total = (hours.val * 60 + minutes.val) as i16;
+            return Ok(minutes.with_val(Offset::Minutes(if sign.val == '-' { -total } else { total })));
+        }
+        let name = one_or_many(read_char_f(|c| c.is_ascii_alphabetic()))(ind)?;
+        let hours = match name.val.iter().collect::<String>().to_ascii_uppercase().as_str()
+        {
+            "UT" | "GMT" => 0,
+            "EDT" => -4,
+            "EST" | "CDT" => -5,
+            "CST" | "MDT" => -6,
+            "MST" | "PDT" => -7,
+            "PST" => -8,
+            military if military.len() == 1 && military != "J" => 0,
+            _ =>
+                return Err(PErr {
+                    pos : ind.pos
+                }),
+        };
+        Ok(name.with_val(Offset::Minutes(hours * 60)))
+    }
+}
+
+// The digits of a fraction of a second, truncated to nanoseconds
+fn nanoseconds(digits : &[char]) -> u32
+{
+    digits
+        .iter()
+        .chain(std::iter::repeat(&'0'))
+        .take(9)
+        .collect::<String>()
+        .parse()
+        .unwrap_or(0)
+}
+
+// Exactly `n` digits, failing where they begin if their value is not within `lo..=hi`
+fn fixed<'a>(n : usize, lo : u32, hi : u32) -> impl Parser<'a, u32>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, u32> {
+        let err = PErr {
+            pos : ind.pos
+        };
+        let digits = ind
+            .text
+            .get(..n)
+            .filter(|d| d.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or(err.clone())?;
+        let val = digits.parse::<u32>().map_err(|_| err.clone())?;
+        if !(lo..=hi).contains(&val)
+        {
+            return Err(err);
+        }
+        Ok(PRes {
+            val,
+            pos : ind.pos.advance(digits),
+            remainder : &ind.text[n..],
+        })
+    }
+}
@@ -0,0 +1,353 @@
+use crate::prelude::*;
+
+// How a configuration file of keys and values is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IniFormat
+{
+    // Characters that begin a comment, which runs to the end of the line. Within an unquoted value a comment must
+    // follow whitespace, so that `url = a#b` keeps its `#`.
+    pub comments :      &'static str,
+    // Characters that separate a key from its value
+    pub separators :    &'static str,
+    // Whether `[section]` headers are allowed
+    pub sections :      bool,
+    // Whether keys may be preceded by `export`, as within a shell script
+    pub export :        bool,
+    // Whether `${key}` within unquoted and double quoted values is replaced by the value of an earlier key, with `$${`
+    // written for a literal `${`
+    pub interpolation : bool,
+}
+
+impl IniFormat
+{
+    pub fn ini() -> Self
+    {
+        Self {
+            comments :      ";#",
+            separators :    "=:",
+            sections :      true,
+            export :        false,
+            interpolation : false,
+        }
+    }
+
+    // A `.env` file of environment variables
+    pub fn dotenv() -> Self
+    {
+        Self {
+            comments :      "#",
+            separators :    "=",
+            sections :      false,
+            export :        true,
+            interpolation : true,
+        }
+    }
+
+    pub fn with_interpolation(mut self) -> Self
+    {
+        self.interpolation = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniEntry
+{
+    pub key :   Spanned<String>,
+    // The value with quotes, escapes, continuations and interpolations resolved, spanning the value as written
+    pub value : Spanned<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniSection
+{
+    // `None` for the keys before the first section header
+    pub name :    Option<Spanned<String>>,
+    pub entries : Vec<IniEntry>,
+}
+
+impl IniSection
+{
+    // The entry for the key, taking the last of any duplicates
+    pub fn entry(&self, key : &str) -> Option<&IniEntry> { self.entries.iter().rev().find(|e| e.key.val == key) }
+
+    pub fn get(&self, key : &str) -> Option<&str> { self.entry(key).map(|e| e.value.val.as_str()) }
+}
+
+// The sections of a configuration file in the order they first appear, where a section that appears more than once has
+// the entries of each
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ini
+{
+    pub sections : Vec<IniSection>,
+}
+
+impl Ini
+{
+    // The keys before the first section header, which are all of them when sections are not allowed
+    pub fn global(&self) -> &IniSection { &self.sections[0] }
+
+    pub fn section(&self, name : &str) -> Option<&IniSection>
+    {
+        self.sections
+            .iter()
+            .find(|s| s.name.as_ref().is_some_and(|n| n.val == name))
+    }
+
+    // The value of the key within the section, where the section `""` holds the keys before the first header
+    pub fn get(&self, section : &str, key : &str) -> Option<&str>
+    {
+        match section
+        {
+            "" => self.global().get(key),
+            _ => self.section(section)?.get(key),
+        }
+    }
+}
+
+// Parses a whole configuration file, failing at the first line that is not well formed or, with interpolation, at the
+// start of a value that refers to a key not defined before it
+pub fn parse_ini(text : &str, format : IniFormat) -> Result<Ini, PErr>
+{
+    ini(format)(&ParserInput::new(text)).map(|r| r.val)
+}
+
+#[inline]
+pub fn ini<'a>(format : IniFormat) -> impl Parser<'a, Ini>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Ini> {
+        let mut doc = Ini {
+            sections : vec![IniSection {
+                name :    None,
+                entries : vec![],
+            }],
+        };
+        let mut current = 0;
+        let mut cur = *ind;
+        while !cur.text.is_empty()
+        {
+            let line = spaces()(&cur)?.to_in();
+            cur = match line.text.chars().next()
+            {
+                Some(c) if format.comments.contains(c) => rest_of_line(format)(&line)?.to_in(),
+                Some('[') if format.sections =>
+                {
+                    let res = header(format)(&line)?;
+                    current = match doc
+                        .sections
+                        .iter()
+                        .position(|s| s.name.as_ref().map(|n| &n.val) == Some(&res.val.val))
+                    {
+                        Some(i) => i,
+                        None =>
+                        {
+                            doc.sections.push(IniSection {
+                                name :    Some(res.val.clone()),
+                                entries : vec![],
+                            });
+                            doc.sections.len() - 1
+                        },
+                    };
+                    res.to_in()
+                },
+                Some(c) if !"\r\n".contains(c) =>
+                {
+                    let res = entry(format)(&line)?;
+                    let next = res.to_in();
+                    let (key, mut value, interpolate) = res.val;
+                    if format.interpolation && interpolate
+                    {
+                        value.val = interpolated(&doc, current, &value)?;
+                    }
+                    doc.sections[current].entries.push(IniEntry {
+                        key,
+                        value,
+                    });
+                    next
+                },
+                _ => line_end()(&line)?.to_in(),
+            };
+        }
+        Ok(PRes {
+            val :       doc,
+            pos :       cur.pos,
+            remainder : cur.text,
+        })
+    }
+}
+
+// Replaces each `${key}` with the value of the key, looking within the current section before the global one. A `$${`
+// is written as a literal `${`, so that a value may hold one without it being interpolated.
+fn interpolated(doc : &Ini, section : usize, value : &Spanned<String>) -> Result<String, PErr>
+{
+    let err = PErr {
+        pos : value.span.start
+    };
+    let mut out = String::new();
+    let mut rest = value.val.as_str();
+    while let Some(start) = rest.find('$')
+    {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(escaped) = after.strip_prefix("${")
+        {
+            out.push_str("${");
+            rest = escaped;
+        }
+        else if let Some(after) = after.strip_prefix('{')
+        {
+            let end = after.find('}').ok_or(err.clone())?;
+            let found = doc.sections[section]
+                .get(&after[..end])
+                .or_else(|| doc.global().get(&after[..end]));
+            out.push_str(found.ok_or(err.clone())?);
+            rest = &after[end + 1..];
+        }
+        else
+        {
+            out.push('$');
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn spaces<'a>() -> impl Parser<'a, Vec<char>> { none_or_many(char_in_str(" \t")) }
+
+// A newline, or the end of the input
+fn line_end<'a>() -> impl Parser<'a, String>
+{
+    or(newline(), succeed_if(always(String::new), |r| r.remainder.is_empty()))
+}
+
+fn comment<'a>(format : IniFormat) -> impl Parser<'a, String>
+{
+    mod_val(
+        then(
+            char_in_str(format.comments),
+            consume_chars_until(no_consume(line_end())),
+            take_right,
+        ),
+        |(text, _)| text,
+    )
+}
+
+// Whatever may follow a header or value on its line
+fn rest_of_line<'a>(format : IniFormat) -> impl Parser<'a, String>
+{
+    then(
+        spaces(),
+        then(maybe(comment(format)), line_end(), take_right),
+        take_right,
+    )
+}
+
+// Words separated by spaces or tabs, such as a key or section name, without any space around them
+fn words<'a>(exclude : &'a str) -> impl Parser<'a, Spanned<String>>
+{
+    let word = one_or_many(read_char_f(move |c| !c.is_whitespace() && !exclude.contains(c)));
+    spanned(text_of(then(
+        word.clone(),
+        none_or_many(then(one_or_many(char_in_str(" \t")), word, take_right)),
+        take_left,
+    )))
+}
+
+fn text_of<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parser<'a, String>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        let res = p(ind)?;
+        Ok(res.with_val(String::from(consumed(ind.text, res.remainder))))
+    }
+}
+
+fn header<'a>(format : IniFormat) -> impl Parser<'a, Spanned<String>>
+{
+    then(
+        then(char_single('['), then(spaces(), words("]"), take_right), take_right),
+        then(
+            spaces(),
+            then(char_single(']'), rest_of_line(format), take_left),
+            take_left,
+        ),
+        take_left,
+    )
+}
+
+// The key and value of an entry, and whether the value may be interpolated
+fn entry<'a>(format : IniFormat) -> impl Parser<'a, (Spanned<String>, Spanned<String>, bool)>
+{
+    let export = maybe(then(keyword("export"), one_or_many(char_in_str(" \t")), take_left));
+    let key = move |ind : &ParserInput<'a>| -> POut<'a, Spanned<String>> {
+        let start = if format.export { export(ind)?.to_in() } else { *ind };
+        words(format.separators)(&start)
+    };
+    let separator = then(
+        spaces(),
+        then(char_in_str(format.separators), spaces(), take_left),
+        take_left,
+    );
+    then(
+        then(key, then(separator, value(format), take_right), left_right),
+        rest_of_line(format),
+        smcomb(|(key, (value, interpolate)), _| (key, value, interpolate)),
+    )
+}
+
+fn value<'a>(format : IniFormat) -> impl Parser<'a, (Spanned<String>, bool)>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, (Spanned<String>, bool)> {
+        let quoted = |string : StringFormat| {
+            spanned(mod_val(
+                string_literal(StringFormat {
+                    multiline : true,
+                    ..string
+                }),
+                |s| s.into_owned(),
+            ))
+        };
+        match ind.text.chars().next()
+        {
+            Some('"') => mod_val(quoted(StringFormat::double()), |v| (v, true))(ind),
+            Some('\'') => mod_val(
+                quoted(StringFormat {
+                    prefix : "",
+                    quote : "'",
+                    ..StringFormat::raw()
+                }),
+                |v| (v, false),
+            )(ind),
+            _ => mod_val(spanned(unquoted(format)), |v| (v, true))(ind),
+        }
+    }
+}
+
+// Everything up to a comment or the end of the line, without the space before either, where a backslash at the end of
+// a line continues the value onto the next line
+fn unquoted<'a>(format : IniFormat) -> impl Parser<'a, String>
+{
+    let continuation = mod_val(
+        then(char_single('\\'), then(newline(), spaces(), take_left), take_left),
+        |_| None,
+    );
+    let end = no_consume(or(
+        mod_val(
+            then(
+                one_or_many(char_in_str(" \t")),
+                char_in_str(format.comments),
+                take_right,
+            ),
+            |_| (),
+        ),
+        mod_val(then(spaces(), line_end(), take_right), |_| ()),
+    ));
+    or(
+        mod_val(no_consume(char_in_str(format.comments)), |_| String::new()),
+        none_or_many_until(
+            or(continuation, mod_val(any_char(), Some)),
+            end,
+            smcomb(|cs : Vec<Option<char>>, _| cs.into_iter().flatten().collect()),
+        ),
+    )
+}
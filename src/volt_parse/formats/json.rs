@@ -0,0 +1,180 @@
+use crate::prelude::*;
+use std::cell::Cell;
+
+// A JSON value as described by RFC 8259, where every value within records where it was parsed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonValue
+{
+    Null,
+    Bool(bool),
+    Number(Float<f64>),
+    String(String),
+    Array(Vec<Spanned<JsonValue>>),
+    // Members in the order they were written, including any duplicate names
+    Object(Vec<(Spanned<String>, Spanned<JsonValue>)>),
+}
+
+impl JsonValue
+{
+    // The value of the member with the given name, taking the last of any duplicates
+    pub fn get(&self, name : &str) -> Option<&JsonValue>
+    {
+        match self
+        {
+            JsonValue::Object(members) => members.iter().rev().find(|(n, _)| n.val == name).map(|(_, v)| &v.val),
+            _ => None,
+        }
+    }
+
+    pub fn at(&self, index : usize) -> Option<&JsonValue>
+    {
+        match self
+        {
+            JsonValue::Array(items) => items.get(index).map(|v| &v.val),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool>
+    {
+        match self
+        {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64>
+    {
+        match self
+        {
+            JsonValue::Number(n) => Some(n.0),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str>
+    {
+        match self
+        {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool { *self == JsonValue::Null }
+}
+
+// Arrays and objects nested deeper than this are rejected, rather than risking overflowing the stack
+pub const JSON_MAX_DEPTH : usize = 128;
+
+// Parses a complete JSON text, failing at the first character that cannot be part of it
+pub fn parse_json(text : &str) -> Result<Spanned<JsonValue>, PErr> { json()(&ParserInput::new(text)).map(|r| r.val) }
+
+// A complete JSON text, being a single value with optional whitespace around it
+#[inline]
+pub fn json<'a>() -> impl Parser<'a, Spanned<JsonValue>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Spanned<JsonValue>> {
+        let res = then(trivia(Trivia::whitespace()), json_value(), take_right)(ind)?;
+        if !res.remainder.is_empty()
+        {
+            return Err(PErr {
+                pos : res.pos
+            });
+        }
+        Ok(res)
+    }
+}
+
+// A single JSON value and any whitespace after it, for embedding JSON within other grammars
+#[inline]
+pub fn json_value<'a>() -> impl Parser<'a, Spanned<JsonValue>> { value(0) }
+
+fn value<'a>(depth : usize) -> impl Parser<'a, Spanned<JsonValue>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Spanned<JsonValue>> {
+        let err = PErr {
+            pos : ind.pos
+        };
+        if depth > JSON_MAX_DEPTH
+        {
+            return Err(err);
+        }
+        // Dispatching on the first character means an error within a value is reported where it is, rather than where
+        // the value began
+        match ind.text.chars().next()
+        {
+            Some('{') => lexeme(spanned(mod_val(list('{', member(depth + 1), '}'), JsonValue::Object)))(ind),
+            Some('[') => lexeme(spanned(mod_val(list('[', value(depth + 1), ']'), JsonValue::Array)))(ind),
+            Some('"') => lexeme(spanned(mod_val(string(), JsonValue::String)))(ind),
+            Some('-' | '0'..='9') => lexeme(spanned(number()))(ind),
+            Some('t') => lexeme(spanned(mod_val(keyword("true"), |_| JsonValue::Bool(true))))(ind),
+            Some('f') => lexeme(spanned(mod_val(keyword("false"), |_| JsonValue::Bool(false))))(ind),
+            Some('n') => lexeme(spanned(mod_val(keyword("null"), |_| JsonValue::Null)))(ind),
+            _ => Err(err),
+        }
+    }
+}
+
+fn member<'a>(depth : usize) -> impl Parser<'a, (Spanned<String>, Spanned<JsonValue>)>
+{
+    then(
+        lexeme(spanned(string())),
+        then(lexeme(char_single(':')), value(depth), take_right),
+        left_right,
+    )
+}
+
+fn string<'a>() -> impl Parser<'a, String> { mod_val(string_literal(StringFormat::json()), |s| s.into_owned()) }
+
+fn number<'a>() -> impl Parser<'a, JsonValue>
+{
+    let digits = one_or_many(ascii_digit());
+    let int = or(
+        mod_val(char_single('0'), |_| ()),
+        mod_val(
+            then(char_range('1', '9'), none_or_many(ascii_digit()), take_left),
+            |_| (),
+        ),
+    );
+    let fraction = maybe(then(char_single('.'), digits.clone(), take_right));
+    let exponent = maybe(then(
+        char_in_str("eE"),
+        then(maybe(char_in_str("+-")), digits, take_right),
+        take_right,
+    ));
+    let literal = then(
+        maybe(char_single('-')),
+        then(int, then(fraction, exponent, take_left), take_left),
+        take_left,
+    );
+    move |ind : &ParserInput<'a>| -> POut<'a, JsonValue> {
+        let res = literal(ind)?;
+        let text = consumed(ind.text, res.remainder);
+        // Every literal that matches the grammar can be read by Rust, though ones too large become infinite
+        Ok(res.with_val(JsonValue::Number(Float(text.parse().unwrap_or(f64::NAN)))))
+    }
+}
+
+// Zero or more items between the brackets, separated by commas.
+//
+// Should the closing bracket be missing where the items stop, the failure of the item that stopped them is reported
+// instead when it is further on, as that is where the mistake is, such as the `]` of `[1, 2,]`.
+fn list<'a, DatT : PResData>(open : char, item : impl Parser<'a, DatT>, close : char) -> impl Parser<'a, Vec<DatT>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Vec<DatT>> {
+        let failed = Cell::new(None);
+        let tracked = |i : &ParserInput<'a>| item(i).inspect_err(|e| failed.set(Some(e.pos)));
+        let items = then(
+            lexeme(char_single(open)),
+            sep_by(tracked, lexeme(char_single(','))),
+            take_right,
+        )(ind)?;
+        char_single(close)(&items.to_in())
+            .map(|end| end.with_val(items.val))
+            .map_err(|e| PErr {
+                pos : failed.get().filter(|pos| *pos > e.pos).unwrap_or(e.pos),
+            })
+    }
+}
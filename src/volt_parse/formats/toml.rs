@@ -0,0 +1,671 @@
+use super::datetime::{date, offset, time, Date, DateTime, Time};
+use crate::prelude::*;
+
+// A value within a TOML 1.0 document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TomlValue
+{
+    String(String),
+    Integer(i64),
+    Float(Float<f64>),
+    Boolean(bool),
+    // An offset or local date time, told apart by whether it has an offset
+    DateTime(DateTime),
+    LocalDate(Date),
+    LocalTime(Time),
+    // Both arrays written as values and arrays of tables
+    Array(Vec<Spanned<TomlValue>>),
+    Table(TomlTable),
+}
+
+// The keys of a table in the order they were first defined, each along with its value
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TomlTable
+{
+    pub entries : Vec<(Spanned<String>, Spanned<TomlValue>)>,
+}
+
+impl TomlTable
+{
+    pub fn get(&self, key : &str) -> Option<&TomlValue> { self.entry(key).map(|(_, v)| &v.val) }
+
+    pub fn entry(&self, key : &str) -> Option<&(Spanned<String>, Spanned<TomlValue>)>
+    {
+        self.entries.iter().find(|(k, _)| k.val == key)
+    }
+
+    // Follows the keys through nested tables, such as `["package", "name"]`
+    pub fn get_path(&self, keys : &[&str]) -> Option<&TomlValue>
+    {
+        let (last, tables) = keys.split_last()?;
+        let mut table = self;
+        for key in tables
+        {
+            table = table.get(key)?.as_table()?;
+        }
+        table.get(last)
+    }
+}
+
+impl TomlValue
+{
+    pub fn as_str(&self) -> Option<&str>
+    {
+        match self
+        {
+            TomlValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i64>
+    {
+        match self
+        {
+            TomlValue::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64>
+    {
+        match self
+        {
+            TomlValue::Float(f) => Some(f.0),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool>
+    {
+        match self
+        {
+            TomlValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Spanned<TomlValue>>>
+    {
+        match self
+        {
+            TomlValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_table(&self) -> Option<&TomlTable>
+    {
+        match self
+        {
+            TomlValue::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+}
+
+// Parses a whole TOML document, failing at the first character that is not well formed, or at a key that would define
+// a value or table for a second time
+pub fn parse_toml(text : &str) -> Result<TomlTable, PErr> { toml()(&ParserInput::new(text)).map(|r| r.val) }
+
+#[inline]
+pub fn toml<'a>() -> impl Parser<'a, TomlTable>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, TomlTable> {
+        let mut root = TableNode::new(Defined::ByHeader, Span::new(ind.pos, ind.pos));
+        // The keys of the most recent header, where an array of tables stands for the last table within it
+        let mut current = vec![];
+        let mut cur = *ind;
+        while !cur.text.is_empty()
+        {
+            let line = spaces()(&cur)?.to_in();
+            let rest = match line.text.chars().next()
+            {
+                Some('[') =>
+                {
+                    let res = header()(&line)?;
+                    let (array, keys, span) = &res.val;
+                    define_table(&mut root, *array, keys, *span)?;
+                    current = keys.iter().map(|k| k.val.clone()).collect();
+                    res.to_in()
+                },
+                Some('#' | '\r' | '\n') => line,
+                _ =>
+                {
+                    let res = keyval()(&line)?;
+                    let next = res.to_in();
+                    let (keys, value) = res.val;
+                    insert(root.table_at(&current), keys, value)?;
+                    next
+                },
+            };
+            cur = rest_of_line()(&rest)?.to_in();
+        }
+        Ok(PRes {
+            val :       root.into_table().1,
+            pos :       cur.pos,
+            remainder : cur.text,
+        })
+    }
+}
+
+// How a table came to exist, which decides whether it may be defined again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Defined
+{
+    // As part of the keys of a header for another table, so it may still be defined by its own header
+    Implicitly,
+    ByHeader,
+    // By dotted keys, so that further dotted keys within the same table may add to it
+    ByDottedKeys,
+}
+
+// A table while the document is being read, before its values are fixed
+struct TableNode
+{
+    defined : Defined,
+    span :    Span,
+    entries : Vec<(Spanned<String>, Node)>,
+}
+
+enum Node
+{
+    // Any value written after a key, including arrays and inline tables, none of which can be added to
+    Value(Spanned<TomlValue>),
+    Table(TableNode),
+    Tables(Span, Vec<TableNode>),
+}
+
+impl TableNode
+{
+    fn new(defined : Defined, span : Span) -> Self
+    {
+        Self {
+            defined,
+            span,
+            entries : vec![],
+        }
+    }
+
+    fn child(&mut self, key : &str) -> Option<&mut Node>
+    {
+        self.entries.iter_mut().find(|(k, _)| k.val == key).map(|(_, n)| n)
+    }
+
+    fn child_or_insert(&mut self, key : &Spanned<String>, node : impl FnOnce() -> Node) -> &mut Node
+    {
+        match self.entries.iter().position(|(k, _)| k.val == key.val)
+        {
+            Some(i) => &mut self.entries[i].1,
+            None =>
+            {
+                self.entries.push((key.clone(), node()));
+                &mut self.entries.last_mut().unwrap().1
+            },
+        }
+    }
+
+    // The table at the end of a path that has already been defined
+    fn table_at(&mut self, keys : &[String]) -> &mut TableNode
+    {
+        match keys.split_first()
+        {
+            None => self,
+            Some((key, rest)) => match self.child(key)
+            {
+                Some(Node::Table(table)) => table.table_at(rest),
+                Some(Node::Tables(_, tables)) if !tables.is_empty() => tables.last_mut().unwrap().table_at(rest),
+                _ => unreachable!("a header defined every table along its path"),
+            },
+        }
+    }
+
+    fn into_table(self) -> (Span, TomlTable)
+    {
+        let entries = self
+            .entries
+            .into_iter()
+            .map(|(key, node)| (key, node.into_value()))
+            .collect();
+        (
+            self.span,
+            TomlTable {
+                entries,
+            },
+        )
+    }
+}
+
+impl Node
+{
+    fn into_value(self) -> Spanned<TomlValue>
+    {
+        let table = |node : TableNode| {
+            let (span, table) = node.into_table();
+            Spanned {
+                val : TomlValue::Table(table),
+                span,
+            }
+        };
+        match self
+        {
+            Node::Value(value) => value,
+            Node::Table(node) => table(node),
+            Node::Tables(span, nodes) => Spanned {
+                val : TomlValue::Array(nodes.into_iter().map(table).collect()),
+                span,
+            },
+        }
+    }
+}
+
+fn redefined(key : &Spanned<String>) -> PErr
+{
+    PErr {
+        pos : key.span.start
+    }
+}
+
+// Defines the table or appends to the array of tables named by a header
+fn define_table(root : &mut TableNode, array : bool, keys : &[Spanned<String>], span : Span) -> Result<(), PErr>
+{
+    let (last, path) = keys.split_last().ok_or(PErr {
+        pos : span.start
+    })?;
+    let mut table = root;
+    for key in path
+    {
+        table = match table.child_or_insert(key, || Node::Table(TableNode::new(Defined::Implicitly, key.span)))
+        {
+            Node::Table(t) => t,
+            Node::Tables(_, ts) if !ts.is_empty() => ts.last_mut().unwrap(),
+            _ => return Err(redefined(key)),
+        };
+    }
+
+    match (array, table.child(&last.val))
+    {
+        (false, None) => table
+            .entries
+            .push((last.clone(), Node::Table(TableNode::new(Defined::ByHeader, span)))),
+        (false, Some(Node::Table(t))) if t.defined == Defined::Implicitly =>
+        {
+            t.defined = Defined::ByHeader;
+            t.span = span;
+        },
+        (true, None) => table.entries.push((
+            last.clone(),
+            Node::Tables(span, vec![TableNode::new(Defined::ByHeader, span)]),
+        )),
+        (true, Some(Node::Tables(_, ts))) => ts.push(TableNode::new(Defined::ByHeader, span)),
+        _ => return Err(redefined(last)),
+    }
+    Ok(())
+}
+
+// Adds a value under its possibly dotted key, where dotted keys may only pass through tables made by dotted keys
+fn insert(table : &mut TableNode, keys : Vec<Spanned<String>>, value : Spanned<TomlValue>) -> Result<(), PErr>
+{
+    let (last, path) = keys.split_last().ok_or(PErr {
+        pos : value.span.start
+    })?;
+    let mut table = table;
+    for key in path
+    {
+        table = match table.child_or_insert(key, || Node::Table(TableNode::new(Defined::ByDottedKeys, key.span)))
+        {
+            Node::Table(t) if t.defined == Defined::ByDottedKeys => t,
+            _ => return Err(redefined(key)),
+        };
+    }
+    if table.child(&last.val).is_some()
+    {
+        return Err(redefined(last));
+    }
+    table.entries.push((last.clone(), Node::Value(value)));
+    Ok(())
+}
+
+fn spaces<'a>() -> impl Parser<'a, Vec<CstToken>> { trivia(Trivia::spaces()) }
+
+// A newline, or the end of the input
+fn line_end<'a>() -> impl Parser<'a, String>
+{
+    or(newline(), succeed_if(always(String::new), |r| r.remainder.is_empty()))
+}
+
+// A comment, which ends at the end of the line or at a control character, which comments may not contain
+fn comment<'a>() -> impl Parser<'a, String>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        let body = char_single('#')(ind)?;
+        let text = body.remainder;
+        let len = text
+            .find(|c : char| (c < ' ' && c != '\t') || c == '\x7f')
+            .unwrap_or(text.len());
+        Ok(PRes {
+            val :       String::from(&text[..len]),
+            pos :       body.pos.advance(&text[..len]),
+            remainder : &text[len..],
+        })
+    }
+}
+
+fn rest_of_line<'a>() -> impl Parser<'a, String>
+{
+    then(spaces(), then(maybe(comment()), line_end(), take_right), take_right)
+}
+
+// Spaces, tabs, newlines and comments, as allowed between the values of an array. A lone `\r` is not a newline.
+fn gap<'a>() -> impl Parser<'a, ()>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, ()> {
+        let mut cur = *ind;
+        loop
+        {
+            cur = spaces()(&cur)?.to_in();
+            if let Ok(nl) = newline()(&cur)
+            {
+                cur = nl.to_in();
+            }
+            else if cur.text.starts_with('#')
+            {
+                cur = comment()(&cur)?.to_in();
+            }
+            else
+            {
+                return Ok(PRes {
+                    val :       (),
+                    pos :       cur.pos,
+                    remainder : cur.text,
+                });
+            }
+        }
+    }
+}
+
+// The keys of a `[table]` or `[[array of tables]]` header, and whether it is the latter
+fn header<'a>() -> impl Parser<'a, (bool, Vec<Spanned<String>>, Span)>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, (bool, Vec<Spanned<String>>, Span)> {
+        let array = ind.text.starts_with("[[");
+        let (open, close) = if array { ("[[", "]]") } else { ("[", "]") };
+        let keys = then(keyword(open), then(spaces(), key(), take_right), take_right)(ind)?;
+        let end = then(spaces(), keyword(close), take_right)(&keys.to_in())?;
+        Ok(end.with_val((array, keys.val.clone(), Span::new(ind.pos, end.pos))))
+    }
+}
+
+fn keyval<'a>() -> impl Parser<'a, (Vec<Spanned<String>>, Spanned<TomlValue>)>
+{
+    then(
+        key(),
+        then(
+            then(spaces(), then(char_single('='), spaces(), take_left), take_left),
+            value(),
+            take_right,
+        ),
+        left_right,
+    )
+}
+
+// A key of one or more parts separated by dots
+fn key<'a>() -> impl Parser<'a, Vec<Spanned<String>>>
+{
+    let dot = then(spaces(), then(char_single('.'), spaces(), take_left), take_left);
+    sep_by1(simple_key(), dot)
+}
+
+fn simple_key<'a>() -> impl Parser<'a, Spanned<String>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Spanned<String>> {
+        match ind.text.chars().next()
+        {
+            Some('"') => spanned(string("\"", true))(ind),
+            Some('\'') => spanned(string("'", false))(ind),
+            _ => spanned(mod_val(
+                one_or_many(read_char_f(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')),
+                |cs| cs.into_iter().collect(),
+            ))(ind),
+        }
+    }
+}
+
+fn value<'a>() -> impl Parser<'a, Spanned<TomlValue>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Spanned<TomlValue>> {
+        let text = ind.text;
+        let string_val = |quote, escapes| spanned(mod_val(string(quote, escapes), TomlValue::String));
+        match text.chars().next()
+        {
+            Some('"') if text.starts_with("\"\"\"") => string_val("\"\"\"", true)(ind),
+            Some('"') => string_val("\"", true)(ind),
+            Some('\'') if text.starts_with("'''") => string_val("'''", false)(ind),
+            Some('\'') => string_val("'", false)(ind),
+            Some('t') => spanned(mod_val(keyword("true"), |_| TomlValue::Boolean(true)))(ind),
+            Some('f') => spanned(mod_val(keyword("false"), |_| TomlValue::Boolean(false)))(ind),
+            Some('[') => spanned(array())(ind),
+            Some('{') => spanned(inline_table())(ind),
+            Some('0'..='9') if is_datetime(text) => spanned(datetime())(ind),
+            Some('0'..='9' | '+' | '-' | 'i' | 'n') => spanned(number())(ind),
+            _ => Err(PErr {
+                pos : ind.pos
+            }),
+        }
+    }
+}
+
+// A string of any of the four kinds, told apart by their quotes and whether they have escapes
+fn string<'a>(quote : &'static str, escapes : bool) -> impl Parser<'a, String>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        let multiline = quote.len() == 3;
+        let res = string_literal(StringFormat {
+            prefix : "",
+            quote,
+            escapes,
+            escape_style : Escapes::Toml,
+            multiline,
+        })(ind)?;
+        let mut val = res.val.into_owned();
+        if !escapes
+        {
+            // Literal strings are taken as written, so the value gives the position of any control character. Multiline
+            // ones may hold newlines, but not a lone `\r`.
+            let control = val
+                .char_indices()
+                .find(|(i, c)| {
+                    let newline = *c == '\n' || val[*i..].starts_with("\r\n");
+                    ((*c < ' ' && *c != '\t') || *c == '\x7f') && !(multiline && newline)
+                })
+                .map(|(i, _)| i);
+            if let Some(i) = control
+            {
+                return Err(PErr {
+                    pos : ind.pos.advance(&ind.text[..quote.len() + i]),
+                });
+            }
+        }
+        // A newline straight after the opening quotes is not part of the string
+        if multiline
+        {
+            let body = &ind.text[quote.len()..];
+            let newline = if body.starts_with("\r\n")
+            {
+                2
+            }
+            else
+            {
+                usize::from(body.starts_with('\n'))
+            };
+            val.drain(..newline);
+        }
+        // Up to two quotes may come before the closing quotes, which the closing quotes would otherwise include
+        let mut remainder = res.remainder;
+        if multiline
+        {
+            let q = &quote[..1];
+            for _ in 0..2
+            {
+                if let Some(rest) = remainder.strip_prefix(q)
+                {
+                    val.push_str(q);
+                    remainder = rest;
+                }
+            }
+        }
+        let consumed = consumed(ind.text, remainder);
+        Ok(PRes {
+            val,
+            pos : ind.pos.advance(consumed),
+            remainder,
+        })
+    }
+}
+
+fn is_datetime(text : &str) -> bool
+{
+    let digits = |n : usize| text.len() > n && text[..n].bytes().all(|b| b.is_ascii_digit());
+    (digits(4) && text[4..].starts_with('-')) || (digits(2) && text[2..].starts_with(':'))
+}
+
+// An offset date time, local date time, local date or local time
+fn datetime<'a>() -> impl Parser<'a, TomlValue>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, TomlValue> {
+        if ind.text[2..].starts_with(':')
+        {
+            return mod_val(time(), TomlValue::LocalTime)(ind);
+        }
+        let day = date()(ind)?;
+        let after = day.to_in();
+        let with_time = after.text.starts_with(['T', 't']) ||
+            (after.text.starts_with(' ') && after.text[1..].starts_with(|c : char| c.is_ascii_digit()));
+        if !with_time
+        {
+            return Ok(day.with_val(TomlValue::LocalDate(day.val)));
+        }
+        let clock = then(any_char(), time(), take_right)(&after)?;
+        let zone = maybe(offset())(&clock.to_in())?;
+        Ok(zone.with_val(TomlValue::DateTime(DateTime {
+            date :   day.val,
+            time :   clock.val,
+            offset : zone.val,
+        })))
+    }
+}
+
+// An integer or float, where decimal integers may not have leading zeros and only decimal numbers may be signed
+fn number<'a>() -> impl Parser<'a, TomlValue>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, TomlValue> {
+        let text = ind.text;
+        let err_at = |i : usize| PErr {
+            pos : ind.pos.advance(&text[..i]),
+        };
+        let unsigned = text.trim_start_matches(['+', '-']);
+        let sign = text.len() - unsigned.len();
+        if sign > 1
+        {
+            return Err(err_at(1));
+        }
+
+        for (word, val) in [("inf", f64::INFINITY), ("nan", f64::NAN)]
+        {
+            if unsigned.starts_with(word)
+            {
+                let end = sign + word.len();
+                return Ok(PRes {
+                    val :       TomlValue::Float(Float(if text.starts_with('-') { -val } else { val })),
+                    pos :       ind.pos.advance(&text[..end]),
+                    remainder : &text[end..],
+                });
+            }
+        }
+
+        let integer = |format : IntFormat| -> POut<'a, TomlValue> {
+            let res = integer_checked::<i64>(format.with_separator('_'))(ind)?;
+            match res.val
+            {
+                Ok(i) => Ok(res.with_val(TomlValue::Integer(i))),
+                Err(_) => Err(err_at(0)),
+            }
+        };
+        if ["0x", "0o", "0b"].iter().any(|p| unsigned.starts_with(p))
+        {
+            return if sign > 0
+            {
+                Err(err_at(0))
+            }
+            else
+            {
+                integer(IntFormat::default().with_prefixes())
+            };
+        }
+        if unsigned.starts_with('0') && unsigned[1..].starts_with(|c : char| c.is_ascii_digit() || c == '_')
+        {
+            return Err(err_at(sign + 1));
+        }
+
+        let float = float_with::<f64>(FloatFormat::default().with_separator('_'))(ind)?;
+        if consumed(text, float.remainder).contains(['.', 'e', 'E'])
+        {
+            return Ok(float.with_val(TomlValue::Float(float.val)));
+        }
+        integer(IntFormat::default())
+    }
+}
+
+// Values separated by commas, which may be spread over many lines with comments between them and a comma after the
+// last value
+fn array<'a>() -> impl Parser<'a, TomlValue>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, TomlValue> {
+        let mut cur = then(char_single('['), gap(), take_right)(ind)?.to_in();
+        let mut items = vec![];
+        loop
+        {
+            if let Ok(end) = char_single(']')(&cur)
+            {
+                return Ok(end.with_val(TomlValue::Array(items)));
+            }
+            let item = value()(&cur)?;
+            let after = gap()(&item.to_in())?.to_in();
+            items.push(item.val);
+            match char_single(',')(&after)
+            {
+                Ok(comma) => cur = gap()(&comma.to_in())?.to_in(),
+                Err(_) => return char_single(']')(&after).map(|end| end.with_val(TomlValue::Array(items))),
+            }
+        }
+    }
+}
+
+// Key values within braces on a single line, which define the whole table so nothing can be added to it later
+fn inline_table<'a>() -> impl Parser<'a, TomlValue>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, TomlValue> {
+        let mut table = TableNode::new(Defined::ByHeader, Span::new(ind.pos, ind.pos));
+        let mut cur = then(char_single('{'), spaces(), take_left)(ind)?.to_in();
+        if let Ok(end) = char_single('}')(&cur)
+        {
+            return Ok(end.with_val(TomlValue::Table(TomlTable::default())));
+        }
+        loop
+        {
+            let kv = keyval()(&cur)?;
+            let after = spaces()(&kv.to_in())?.to_in();
+            let (keys, value) = kv.val;
+            insert(&mut table, keys, value)?;
+            match char_single(',')(&after)
+            {
+                Ok(comma) => cur = spaces()(&comma.to_in())?.to_in(),
+                Err(_) =>
+                {
+                    let end = char_single('}')(&after)?;
+                    return Ok(end.with_val(TomlValue::Table(table.into_table().1)));
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use crate::prelude::*;
+use std::{
+    fmt::Display,
+    net::{Ipv4Addr, Ipv6Addr},
+    string::FromUtf8Error,
+};
+
+// The characters other than letters and digits that never need to be percent encoded
+const UNRESERVED : &str = "-._~";
+const SUB_DELIMS : &str = "!$&'()*+,;=";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Host
+{
+    // A registered name such as `example.com` as written, which may be empty or percent encoded
+    Name(String),
+    Ipv4(Ipv4Addr),
+    // Written within brackets, such as `[::1]`
+    Ipv6(Ipv6Addr),
+    // A future version of IP address within brackets, such as `[v7.fe80::1]`, kept as written without its brackets
+    IpFuture(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Authority
+{
+    pub userinfo : Option<Spanned<String>>,
+    // The span includes the brackets of an IP literal
+    pub host :     Spanned<Host>,
+    // `None` when there is no port, or when the colon before it has no digits after it
+    pub port :     Option<Spanned<u16>>,
+}
+
+// A URI or relative reference as in RFC 3986, each component of which is kept as written, still percent encoded,
+// along with the span of it that excludes the delimiters around it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uri
+{
+    // `None` for a relative reference
+    pub scheme :    Option<Spanned<String>>,
+    pub authority : Option<Authority>,
+    // Possibly empty, and always empty or beginning with `/` when there is an authority
+    pub path :      Spanned<String>,
+    pub query :     Option<Spanned<String>>,
+    pub fragment :  Option<Spanned<String>>,
+}
+
+impl Uri
+{
+    pub fn is_relative(&self) -> bool { self.scheme.is_none() }
+
+    // The segments of the path with percent encoding decoded, without the empty segment before a leading `/`
+    pub fn segments(&self) -> Result<Vec<String>, FromUtf8Error>
+    {
+        if self.path.val.is_empty()
+        {
+            return Ok(vec![]);
+        }
+        let path = self.path.val.strip_prefix('/').unwrap_or(&self.path.val);
+        path.split('/').map(percent_decode).collect()
+    }
+
+    // The target of this reference when it is found within the document at `base`, as in section 5.2 of RFC 3986, or
+    // `None` if `base` has no scheme. The spans of the target are within its `to_string()`.
+    pub fn resolve(&self, base : &Uri) -> Option<Uri>
+    {
+        base.scheme.as_ref()?;
+        let with_path = |uri : &Uri, path : String| Spanned {
+            val :  path,
+            span : uri.path.span,
+        };
+        let mut target = if self.scheme.is_some()
+        {
+            Uri {
+                path : with_path(self, remove_dot_segments(&self.path.val)),
+                ..self.clone()
+            }
+        }
+        else if self.authority.is_some()
+        {
+            Uri {
+                scheme : base.scheme.clone(),
+                path : with_path(self, remove_dot_segments(&self.path.val)),
+                ..self.clone()
+            }
+        }
+        else
+        {
+            let path = if self.path.val.is_empty()
+            {
+                base.path.val.clone()
+            }
+            else if self.path.val.starts_with('/')
+            {
+                remove_dot_segments(&self.path.val)
+            }
+            else
+            {
+                remove_dot_segments(&merge(base, &self.path.val))
+            };
+            Uri {
+                scheme :    base.scheme.clone(),
+                authority : base.authority.clone(),
+                path :      with_path(self, path),
+                query :     if self.path.val.is_empty()
+                {
+                    self.query.clone().or_else(|| base.query.clone())
+                }
+                else
+                {
+                    self.query.clone()
+                },
+                fragment :  None,
+            }
+        };
+        target.fragment = self.fragment.clone();
+        parse_uri(&target.to_string()).ok()
+    }
+}
+
+// The path of a relative reference appended to the directory of the base path
+fn merge(base : &Uri, path : &str) -> String
+{
+    if base.authority.is_some() && base.path.val.is_empty()
+    {
+        return format!("/{path}");
+    }
+    match base.path.val.rfind('/')
+    {
+        Some(i) => format!("{}{path}", &base.path.val[..=i]),
+        None => String::from(path),
+    }
+}
+
+// Resolves the `.` and `..` segments of a path, as in section 5.2.4 of RFC 3986
+fn remove_dot_segments(path : &str) -> String
+{
+    let mut input = path;
+    let mut output = String::new();
+    let pop = |output : &mut String| output.truncate(output.rfind('/').unwrap_or(0));
+    while !input.is_empty()
+    {
+        if let Some(rest) = input.strip_prefix("../").or_else(|| input.strip_prefix("./"))
+        {
+            input = rest;
+        }
+        else if input.starts_with("/./") || input == "/."
+        {
+            input = if input == "/." { "/" } else { &input[2..] };
+        }
+        else if input.starts_with("/../") || input == "/.."
+        {
+            input = if input == "/.." { "/" } else { &input[3..] };
+            pop(&mut output);
+        }
+        else if input == "." || input == ".."
+        {
+            input = "";
+        }
+        else
+        {
+            let start = usize::from(input.starts_with('/'));
+            let end = input[start..].find('/').map_or(input.len(), |i| i + start);
+            output.push_str(&input[..end]);
+            input = &input[end..];
+        }
+    }
+    output
+}
+
+// Decodes each `%` followed by two hexadecimal digits into the byte they encode, failing if the bytes are not UTF-8
+pub fn percent_decode(text : &str) -> Result<String, FromUtf8Error>
+{
+    let bytes = text.as_bytes();
+    let mut out = vec![];
+    let mut i = 0;
+    while i < bytes.len()
+    {
+        let hex = text
+            .get(i + 1..i + 3)
+            .filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex)
+        {
+            (b'%', Some(b)) =>
+            {
+                out.push(b);
+                i += 3;
+            },
+            (b, _) =>
+            {
+                out.push(b);
+                i += 1;
+            },
+        }
+    }
+    String::from_utf8(out)
+}
+
+impl Display for Host
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Host::Name(name) => write!(f, "{name}"),
+            Host::Ipv4(addr) => write!(f, "{addr}"),
+            Host::Ipv6(addr) => write!(f, "[{addr}]"),
+            Host::IpFuture(addr) => write!(f, "[{addr}]"),
+        }
+    }
+}
+
+impl Display for Authority
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        if let Some(userinfo) = &self.userinfo
+        {
+            write!(f, "{}@", userinfo.val)?;
+        }
+        write!(f, "{}", self.host.val)?;
+        if let Some(port) = &self.port
+        {
+            write!(f, ":{}", port.val)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Uri
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        if let Some(scheme) = &self.scheme
+        {
+            write!(f, "{}:", scheme.val)?;
+        }
+        match &self.authority
+        {
+            Some(authority) => write!(f, "//{authority}")?,
+            // A path beginning with `//` would be read as an authority
+            None if self.path.val.starts_with("//") => write!(f, "/.")?,
+            None => (),
+        }
+        write!(f, "{}", self.path.val)?;
+        if let Some(query) = &self.query
+        {
+            write!(f, "?{}", query.val)?;
+        }
+        if let Some(fragment) = &self.fragment
+        {
+            write!(f, "#{}", fragment.val)?;
+        }
+        Ok(())
+    }
+}
+
+// Parses a whole URI or relative reference, failing at the first character that cannot be part of it
+pub fn parse_uri(text : &str) -> Result<Uri, PErr>
+{
+    let res = uri()(&ParserInput::new(text))?;
+    if !res.remainder.is_empty()
+    {
+        return Err(PErr {
+            pos : res.pos
+        });
+    }
+    Ok(res.val)
+}
+
+// A URI or relative reference, which ends at the first character that cannot be part of it, such as a space
+#[inline]
+pub fn uri<'a>() -> impl Parser<'a, Uri>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Uri> {
+        let scheme = scheme()(ind).ok();
+        let rest = scheme.as_ref().map_or(*ind, |s| s.to_in());
+
+        let authority = match rest.text.strip_prefix("//")
+        {
+            Some(_) => Some(then(keyword("//"), authority(), take_right)(&rest)?),
+            None => None,
+        };
+        let rest = authority.as_ref().map_or(rest, |a| a.to_in());
+
+        let path = spanned(chars_of(":@/"))(&rest)?;
+        let err_at = |i : usize| PErr {
+            pos : rest.pos.advance(&rest.text[..i]),
+        };
+        if authority.is_some() && !path.val.val.is_empty() && !path.val.val.starts_with('/')
+        {
+            return Err(err_at(0));
+        }
+        // Without a scheme or authority, a colon within the first segment would make it a scheme
+        if scheme.is_none() && authority.is_none()
+        {
+            let first = path.val.val.split('/').next().unwrap_or_default();
+            if let Some(i) = first.find(':')
+            {
+                return Err(err_at(i));
+            }
+        }
+
+        let query = maybe(then(char_single('?'), spanned(chars_of(":@/?")), take_right))(&path.to_in())?;
+        let fragment = maybe(then(char_single('#'), spanned(chars_of(":@/?")), take_right))(&query.to_in())?;
+        Ok(PRes {
+            val :       Uri {
+                scheme :    scheme.map(|s| s.val),
+                authority : authority.map(|a| a.val),
+                path :      path.val,
+                query :     query.val,
+                fragment :  fragment.val,
+            },
+            pos :       fragment.pos,
+            remainder : fragment.remainder,
+        })
+    }
+}
+
+// Letters, digits, the unreserved and sub-delimiter characters, the `extra` characters and percent encoded bytes,
+// failing at a `%` that is not followed by two hexadecimal digits
+fn chars_of<'a>(extra : &'static str) -> impl Parser<'a, String>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        let text = ind.text;
+        let mut i = 0;
+        while let Some(c) = text[i..].chars().next()
+        {
+            if c == '%'
+            {
+                if !text
+                    .get(i + 1..i + 3)
+                    .is_some_and(|h| h.bytes().all(|b| b.is_ascii_hexdigit()))
+                {
+                    return Err(PErr {
+                        pos : ind.pos.advance(&text[..i]),
+                    });
+                }
+                i += 3;
+                continue;
+            }
+            if !(c.is_ascii_alphanumeric() || UNRESERVED.contains(c) || SUB_DELIMS.contains(c) || extra.contains(c))
+            {
+                break;
+            }
+            i += 1;
+        }
+        Ok(PRes {
+            val :       String::from(&text[..i]),
+            pos :       ind.pos.advance(&text[..i]),
+            remainder : &text[i..],
+        })
+    }
+}
+
+// A letter followed by letters, digits, `+`, `-` and `.`, and then the colon that ends it
+fn scheme<'a>() -> impl Parser<'a, Spanned<String>>
+{
+    let name = then(
+        read_char_f(|c| c.is_ascii_alphabetic()),
+        none_or_many(read_char_f(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))),
+        smcomb(|first, rest : Vec<char>| std::iter::once(first).chain(rest).collect::<String>()),
+    );
+    then(spanned(name), char_single(':'), take_left)
+}
+
+fn authority<'a>() -> impl Parser<'a, Authority>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Authority> {
+        let userinfo = then(spanned(chars_of(":")), char_single('@'), take_left)(ind).ok();
+        let rest = userinfo.as_ref().map_or(*ind, |u| u.to_in());
+        let host = spanned(host())(&rest)?;
+        let port = maybe(then(char_single(':'), maybe(spanned(port())), take_right))(&host.to_in())?;
+        Ok(PRes {
+            val :       Authority {
+                userinfo : userinfo.map(|u| u.val),
+                host :     host.val,
+                port :     port.val.flatten(),
+            },
+            pos :       port.pos,
+            remainder : port.remainder,
+        })
+    }
+}
+
+// An IP literal within brackets, an IPv4 address, or otherwise a registered name, which fails at the start of an IP
+// literal that is not valid
+fn host<'a>() -> impl Parser<'a, Host>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Host> {
+        let text = ind.text;
+        if !text.starts_with('[')
+        {
+            let name = chars_of("")(ind)?;
+            let host = match name.val.parse::<Ipv4Addr>()
+            {
+                Ok(addr) => Host::Ipv4(addr),
+                Err(_) => Host::Name(name.val.clone()),
+            };
+            return Ok(name.with_val(host));
+        }
+
+        let err = PErr {
+            pos : ind.pos.advance("["),
+        };
+        let close = text.find(']').ok_or(err.clone())?;
+        let literal = &text[1..close];
+        let host = match literal.strip_prefix(['v', 'V'])
+        {
+            Some(future) if is_ip_future(future) => Host::IpFuture(String::from(literal)),
+            Some(_) => return Err(err),
+            None => Host::Ipv6(literal.parse().map_err(|_| err)?),
+        };
+        Ok(PRes {
+            val :       host,
+            pos :       ind.pos.advance(&text[..=close]),
+            remainder : &text[close + 1..],
+        })
+    }
+}
+
+// Hexadecimal digits for the version, a dot, and then the address
+fn is_ip_future(text : &str) -> bool
+{
+    let Some((version, addr)) = text.split_once('.')
+    else
+    {
+        return false;
+    };
+    !version.is_empty() &&
+        version.bytes().all(|b| b.is_ascii_hexdigit()) &&
+        !addr.is_empty() &&
+        addr.chars()
+            .all(|c| c.is_ascii_alphanumeric() || UNRESERVED.contains(c) || SUB_DELIMS.contains(c) || c == ':')
+}
+
+// Digits that fail where they begin if they are too large for a port
+fn port<'a>() -> impl Parser<'a, u16>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, u16> {
+        let digits = one_or_many(ascii_digit())(ind)?;
+        let port = digits.val.iter().collect::<String>().parse().map_err(|_| PErr {
+            pos : ind.pos
+        })?;
+        Ok(digits.with_val(port))
+    }
+}
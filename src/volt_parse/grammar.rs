@@ -0,0 +1,16 @@
+// Grammars that are described as data rather than as Rust closures, so that they can be loaded at runtime and inspected
+pub mod analysis;
+pub mod build;
+pub mod display;
+pub mod error;
+pub mod expr;
+pub mod interp;
+pub mod lint;
+pub mod peg;
+pub mod railroad;
+
+pub use analysis::*;
+pub use error::*;
+pub use expr::*;
+pub use lint::*;
+pub use peg::*;
@@ -0,0 +1,280 @@
+use super::expr::{Expr, Grammar};
+use std::collections::{BTreeSet, HashMap};
+
+// The characters that a match may begin with, and whether it may match nothing at all
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FirstSet
+{
+    // Sorted, non-overlapping and non-adjacent inclusive ranges
+    pub ranges :   Vec<(char, char)>,
+    pub nullable : bool,
+}
+
+const MAX : u32 = char::MAX as u32;
+
+fn to_char(c : u32) -> char
+{
+    // Ranges are only ever split around the surrogates, so this lands on a valid char
+    char::from_u32(c).unwrap_or(if c < 0xE000 { '\u{D7FF}' } else { '\u{E000}' })
+}
+
+fn next(c : char) -> u32
+{
+    if c == '\u{D7FF}'
+    {
+        0xE000
+    }
+    else
+    {
+        c as u32 + 1
+    }
+}
+
+fn prev(c : char) -> u32
+{
+    if c == '\u{E000}'
+    {
+        0xD7FF
+    }
+    else
+    {
+        c as u32 - 1
+    }
+}
+
+// Sorts and merges the ranges
+pub(super) fn normalize(mut ranges : Vec<(char, char)>) -> Vec<(char, char)>
+{
+    ranges.retain(|(lo, hi)| lo <= hi);
+    ranges.sort();
+    let mut out : Vec<(char, char)> = vec![];
+    for (lo, hi) in ranges
+    {
+        match out.last_mut()
+        {
+            Some(last) if (lo as u32) <= next(last.1) => last.1 = last.1.max(hi),
+            _ => out.push((lo, hi)),
+        }
+    }
+    out
+}
+
+pub(super) fn complement(ranges : &[(char, char)]) -> Vec<(char, char)>
+{
+    let mut out = vec![];
+    let mut start = 0u32;
+    for (lo, hi) in normalize(ranges.to_vec())
+    {
+        if (lo as u32) > start
+        {
+            out.push((to_char(start), to_char(prev(lo))));
+        }
+        start = next(hi);
+    }
+    if start <= MAX
+    {
+        out.push((to_char(start), char::MAX));
+    }
+    out
+}
+
+impl FirstSet
+{
+    pub fn chars(ranges : Vec<(char, char)>) -> Self
+    {
+        Self {
+            ranges :   normalize(ranges),
+            nullable : false,
+        }
+    }
+
+    pub fn any() -> Self { Self::chars(vec![('\0', char::MAX)]) }
+
+    pub fn contains(&self, c : char) -> bool { self.ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)) }
+
+    // Whether any character may begin a match of both sets
+    pub fn overlaps(&self, other : &FirstSet) -> bool
+    {
+        self.ranges
+            .iter()
+            .any(|(alo, ahi)| other.ranges.iter().any(|(blo, bhi)| alo <= bhi && blo <= ahi))
+    }
+
+    fn add_chars(&mut self, other : &FirstSet)
+    {
+        let mut ranges = self.ranges.clone();
+        ranges.extend(other.ranges.iter().copied());
+        self.ranges = normalize(ranges);
+    }
+}
+
+impl Grammar
+{
+    // The first set of every rule
+    pub fn first_sets(&self) -> HashMap<String, FirstSet>
+    {
+        let mut sets = self
+            .rules
+            .iter()
+            .map(|r| (r.name.clone(), FirstSet::default()))
+            .collect::<HashMap<_, _>>();
+
+        // Rules only ever grow, so repeat until nothing changes
+        loop
+        {
+            let mut changed = false;
+            for rule in &self.rules
+            {
+                let set = first(&rule.expr, &sets);
+                if sets[&rule.name] != set
+                {
+                    sets.insert(rule.name.clone(), set);
+                    changed = true;
+                }
+            }
+            if !changed
+            {
+                return sets;
+            }
+        }
+    }
+
+    // The first set of an expression within this grammar
+    pub fn first(&self, expr : &Expr) -> FirstSet { first(expr, &self.first_sets()) }
+
+    // The rules which may match without consuming anything
+    pub fn nullable_rules(&self) -> BTreeSet<String>
+    {
+        self.first_sets()
+            .into_iter()
+            .filter(|(_, set)| set.nullable)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    // The rules directly referred to by the named rule, in the order they are first referred to
+    pub fn references(&self, rule : &str) -> Vec<String>
+    {
+        let mut out = vec![];
+        if let Some(rule) = self.rule(rule)
+        {
+            rule.expr.visit(&mut |e| {
+                if let Expr::Rule(name) = e
+                {
+                    if !out.contains(name)
+                    {
+                        out.push(name.clone());
+                    }
+                }
+            });
+        }
+        out
+    }
+
+    // The rules that directly refer to the named rule
+    pub fn referenced_by(&self, rule : &str) -> Vec<String>
+    {
+        self.rules
+            .iter()
+            .filter(|r| self.references(&r.name).iter().any(|n| n == rule))
+            .map(|r| r.name.clone())
+            .collect()
+    }
+
+    // The rules which can be reached from the starting rule, including the starting rule
+    pub fn reachable(&self) -> BTreeSet<String>
+    {
+        let mut seen = BTreeSet::new();
+        let mut todo = vec![self.start().name.clone()];
+        while let Some(name) = todo.pop()
+        {
+            if seen.insert(name.clone())
+            {
+                todo.extend(self.references(&name));
+            }
+        }
+        seen
+    }
+
+    // Markdown documentation with a section per rule
+    pub fn to_markdown(&self) -> String
+    {
+        let sets = self.first_sets();
+        let mut out = String::new();
+        for rule in &self.rules
+        {
+            out.push_str(&format!("## `{}`\n\n```peg\n{rule}\n```\n\n", rule.name));
+            let used_by = self.referenced_by(&rule.name);
+            if !used_by.is_empty()
+            {
+                let names = used_by.iter().map(|n| format!("`{n}`")).collect::<Vec<_>>();
+                out.push_str(&format!("Used by: {}\n\n", names.join(", ")));
+            }
+            if sets[&rule.name].nullable
+            {
+                out.push_str("May match empty input.\n\n");
+            }
+        }
+        out
+    }
+}
+
+pub(super) fn first(expr : &Expr, sets : &HashMap<String, FirstSet>) -> FirstSet
+{
+    match expr
+    {
+        Expr::Literal(lit) => match lit.chars().next()
+        {
+            Some(c) => FirstSet::chars(vec![(c, c)]),
+            None => FirstSet {
+                ranges :   vec![],
+                nullable : true,
+            },
+        },
+        Expr::Class {
+            ranges,
+            negated,
+        } => FirstSet::chars(if *negated { complement(ranges) } else { ranges.clone() }),
+        Expr::Any => FirstSet::any(),
+        Expr::Rule(name) => sets.get(name).cloned().unwrap_or_default(),
+        Expr::Seq(es) =>
+        {
+            let mut out = FirstSet {
+                ranges :   vec![],
+                nullable : true,
+            };
+            for e in es
+            {
+                let set = first(e, sets);
+                out.add_chars(&set);
+                if !set.nullable
+                {
+                    out.nullable = false;
+                    break;
+                }
+            }
+            out
+        },
+        Expr::Choice(es) =>
+        {
+            let mut out = FirstSet::default();
+            for e in es
+            {
+                let set = first(e, sets);
+                out.add_chars(&set);
+                out.nullable |= set.nullable;
+            }
+            out
+        },
+        Expr::ZeroOrMore(e) | Expr::Optional(e) => FirstSet {
+            nullable : true,
+            ..first(e, sets)
+        },
+        Expr::OneOrMore(e) => first(e, sets),
+        // Predicates never consume anything, what follows them decides what is consumed
+        Expr::And(_) | Expr::Not(_) => FirstSet {
+            ranges :   vec![],
+            nullable : true,
+        },
+    }
+}
@@ -0,0 +1,109 @@
+// Builds grammar expressions using the same names as the parser combinators, so that a grammar written with closures
+// can be rewritten as data with few changes:
+//
+//   use volt_parse::grammar::build::*;
+//
+//   let grammar = Grammar::new(vec![
+//       define("list", then(keyword("["), then(maybe(rule("items")), keyword("]")))),
+//       define("items", then(rule("item"), none_or_many(then(keyword(","), rule("item"))))),
+//       define("item", one_or_many(char_range('a', 'z'))),
+//   ]);
+use super::expr::{Expr, Rule};
+
+pub fn define(name : &str, expr : Expr) -> Rule
+{
+    Rule {
+        name : name.to_string(),
+        expr,
+    }
+}
+
+pub fn rule(name : &str) -> Expr { Expr::Rule(name.to_string()) }
+
+pub fn keyword(word : &str) -> Expr { Expr::Literal(word.to_string()) }
+
+pub fn char_single(ch : char) -> Expr { char_range(ch, ch) }
+
+pub fn char_range(lo : char, hi : char) -> Expr
+{
+    Expr::Class {
+        ranges :  vec![(lo, hi)],
+        negated : false,
+    }
+}
+
+pub fn char_in_str(chars : &str) -> Expr
+{
+    Expr::Class {
+        ranges :  chars.chars().map(|c| (c, c)).collect(),
+        negated : false,
+    }
+}
+
+pub fn char_not_in_str(chars : &str) -> Expr
+{
+    Expr::Class {
+        ranges :  chars.chars().map(|c| (c, c)).collect(),
+        negated : true,
+    }
+}
+
+pub fn any_char() -> Expr { Expr::Any }
+
+// Sequences are flattened, so `then(a, then(b, c))` is the same as `seq(vec![a, b, c])`
+pub fn then(a : Expr, b : Expr) -> Expr { seq(vec![a, b]) }
+
+pub fn seq(es : Vec<Expr>) -> Expr
+{
+    let mut out = vec![];
+    for e in es
+    {
+        match e
+        {
+            Expr::Seq(inner) => out.extend(inner),
+            e => out.push(e),
+        }
+    }
+    if out.len() == 1
+    {
+        out.remove(0)
+    }
+    else
+    {
+        Expr::Seq(out)
+    }
+}
+
+// Choices are flattened, so `or(a, or(b, c))` is the same as `choice(vec![a, b, c])`
+pub fn or(a : Expr, b : Expr) -> Expr { choice(vec![a, b]) }
+
+pub fn choice(es : Vec<Expr>) -> Expr
+{
+    let mut out = vec![];
+    for e in es
+    {
+        match e
+        {
+            Expr::Choice(inner) => out.extend(inner),
+            e => out.push(e),
+        }
+    }
+    if out.len() == 1
+    {
+        out.remove(0)
+    }
+    else
+    {
+        Expr::Choice(out)
+    }
+}
+
+pub fn none_or_many(e : Expr) -> Expr { Expr::ZeroOrMore(Box::new(e)) }
+
+pub fn one_or_many(e : Expr) -> Expr { Expr::OneOrMore(Box::new(e)) }
+
+pub fn maybe(e : Expr) -> Expr { Expr::Optional(Box::new(e)) }
+
+pub fn no_consume(e : Expr) -> Expr { Expr::And(Box::new(e)) }
+
+pub fn not(e : Expr) -> Expr { Expr::Not(Box::new(e)) }
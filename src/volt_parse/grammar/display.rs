@@ -0,0 +1,126 @@
+use super::expr::{Expr, Grammar, Rule};
+use std::fmt::{Display, Formatter, Result};
+
+// Expressions and grammars are displayed using the same PEG syntax that `Grammar::from_peg` reads
+
+// How tightly each kind of expression binds, where looser expressions need parentheses when nested in tighter ones
+fn precedence(e : &Expr) -> u8
+{
+    match e
+    {
+        Expr::Seq(es) | Expr::Choice(es) if es.len() == 1 => precedence(&es[0]),
+        Expr::Choice(_) => 0,
+        Expr::Seq(es) if es.is_empty() => 4,
+        Expr::Seq(_) => 1,
+        Expr::And(_) | Expr::Not(_) => 2,
+        Expr::ZeroOrMore(_) | Expr::OneOrMore(_) | Expr::Optional(_) => 3,
+        Expr::Literal(_) |
+        Expr::Class {
+            ..
+        } |
+        Expr::Any |
+        Expr::Rule(_) => 4,
+    }
+}
+
+fn nested(e : &Expr, min : u8, f : &mut Formatter<'_>) -> Result
+{
+    if precedence(e) < min
+    {
+        write!(f, "({e})")
+    }
+    else
+    {
+        write!(f, "{e}")
+    }
+}
+
+fn escape(c : char, specials : &str) -> String
+{
+    match c
+    {
+        '\n' => String::from("\\n"),
+        '\r' => String::from("\\r"),
+        '\t' => String::from("\\t"),
+        '\0' => String::from("\\0"),
+        '\\' => String::from("\\\\"),
+        c if specials.contains(c) => format!("\\{c}"),
+        c if c.is_control() => format!("\\u{{{:x}}}", c as u32),
+        c => c.to_string(),
+    }
+}
+
+impl Display for Expr
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> Result
+    {
+        match self
+        {
+            Expr::Literal(lit) =>
+            {
+                write!(f, "\"{}\"", lit.chars().map(|c| escape(c, "\"")).collect::<String>())
+            },
+            Expr::Class {
+                ranges,
+                negated,
+            } =>
+            {
+                f.write_str(if *negated { "[^" } else { "[" })?;
+                for (lo, hi) in ranges
+                {
+                    f.write_str(&escape(*lo, "[]-^"))?;
+                    if lo != hi
+                    {
+                        write!(f, "-{}", escape(*hi, "[]-^"))?;
+                    }
+                }
+                f.write_str("]")
+            },
+            Expr::Any => f.write_str("."),
+            Expr::Rule(name) => f.write_str(name),
+            Expr::Seq(es) =>
+            {
+                if es.is_empty()
+                {
+                    return f.write_str("()");
+                }
+                for (i, e) in es.iter().enumerate()
+                {
+                    if i > 0
+                    {
+                        f.write_str(" ")?;
+                    }
+                    nested(e, 2, f)?;
+                }
+                Ok(())
+            },
+            Expr::Choice(es) =>
+            {
+                for (i, e) in es.iter().enumerate()
+                {
+                    if i > 0
+                    {
+                        f.write_str(" / ")?;
+                    }
+                    nested(e, 1, f)?;
+                }
+                Ok(())
+            },
+            Expr::ZeroOrMore(e) => nested(e, 4, f).and_then(|_| f.write_str("*")),
+            Expr::OneOrMore(e) => nested(e, 4, f).and_then(|_| f.write_str("+")),
+            Expr::Optional(e) => nested(e, 4, f).and_then(|_| f.write_str("?")),
+            Expr::And(e) => f.write_str("&").and_then(|_| nested(e, 3, f)),
+            Expr::Not(e) => f.write_str("!").and_then(|_| nested(e, 3, f)),
+        }
+    }
+}
+
+impl Display for Rule
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> Result { write!(f, "{} <- {}", self.name, self.expr) }
+}
+
+impl Display for Grammar
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> Result { self.rules.iter().try_for_each(|r| writeln!(f, "{r}")) }
+}
@@ -0,0 +1,43 @@
+use crate::volt_parse::parser::PErr;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrammarError
+{
+    // The grammar text itself could not be parsed
+    Syntax(PErr),
+    // A rule refers to another rule that does not exist
+    UndefinedRule
+    {
+        name :          String,
+        referenced_by : String,
+    },
+    DuplicateRule(String),
+    // A parser was asked to start at a rule that does not exist
+    UnknownStartRule(String),
+    // The grammar does not define any rules
+    Empty,
+}
+
+impl Display for GrammarError
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            GrammarError::Syntax(err) =>
+            {
+                write!(f, "grammar syntax error at {}:{}", err.pos.line, err.pos.column)
+            },
+            GrammarError::UndefinedRule {
+                name,
+                referenced_by,
+            } => write!(f, "rule '{referenced_by}' refers to undefined rule '{name}'"),
+            GrammarError::DuplicateRule(name) => write!(f, "rule '{name}' is defined more than once"),
+            GrammarError::UnknownStartRule(name) => write!(f, "there is no rule '{name}' to start at"),
+            GrammarError::Empty => write!(f, "grammar does not define any rules"),
+        }
+    }
+}
+
+impl std::error::Error for GrammarError {}
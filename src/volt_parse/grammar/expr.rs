@@ -0,0 +1,146 @@
+use super::error::GrammarError;
+use crate::volt_parse::span::Span;
+use std::collections::HashSet;
+
+// A single parsing expression, mirroring the core combinators
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr
+{
+    // Matches the text exactly, like `keyword`
+    Literal(String),
+    // Matches a single character within (or when negated, outside of) any of the inclusive ranges
+    Class
+    {
+        ranges :  Vec<(char, char)>,
+        negated : bool,
+    },
+    // Matches any single character, like `any_char`
+    Any,
+    // Matches the named rule
+    Rule(String),
+    // Matches each expression one after another, like `then`
+    Seq(Vec<Expr>),
+    // Matches the first expression that succeeds, like `or`
+    Choice(Vec<Expr>),
+    // Like `none_or_many`
+    ZeroOrMore(Box<Expr>),
+    // Like `one_or_many`
+    OneOrMore(Box<Expr>),
+    // Like `maybe`
+    Optional(Box<Expr>),
+    // Succeeds if the expression would match, without consuming anything, like `no_consume`
+    And(Box<Expr>),
+    // Succeeds if the expression would not match, without consuming anything, like `not`
+    Not(Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule
+{
+    pub name : String,
+    pub expr : Expr,
+}
+
+// A set of named rules, the first of which is the default starting rule. The rules can only be set through `new`, so
+// that every rule referred to is defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grammar
+{
+    pub(super) rules : Vec<Rule>,
+}
+
+// What a grammar produces when parsing, one node per matched rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node
+{
+    pub rule :     String,
+    pub span :     Span,
+    pub text :     String,
+    pub children : Vec<Node>,
+}
+
+impl Expr
+{
+    // Calls `f` on this expression and every expression nested within it
+    pub fn visit<'e>(&'e self, f : &mut impl FnMut(&'e Expr))
+    {
+        f(self);
+        match self
+        {
+            Expr::Seq(es) | Expr::Choice(es) => es.iter().for_each(|e| e.visit(f)),
+            Expr::ZeroOrMore(e) | Expr::OneOrMore(e) | Expr::Optional(e) | Expr::And(e) | Expr::Not(e) => e.visit(f),
+            Expr::Literal(_) |
+            Expr::Class {
+                ..
+            } |
+            Expr::Any |
+            Expr::Rule(_) =>
+            {},
+        }
+    }
+}
+
+impl Grammar
+{
+    // Creates a grammar, ensuring that every referenced rule exists and that no rule is defined twice
+    pub fn new(rules : Vec<Rule>) -> Result<Self, GrammarError>
+    {
+        if rules.is_empty()
+        {
+            return Err(GrammarError::Empty);
+        }
+
+        let mut names = HashSet::new();
+        for rule in &rules
+        {
+            if !names.insert(rule.name.as_str())
+            {
+                return Err(GrammarError::DuplicateRule(rule.name.clone()));
+            }
+        }
+
+        for rule in &rules
+        {
+            let mut undefined = None;
+            rule.expr.visit(&mut |e| match e
+            {
+                Expr::Rule(name) if undefined.is_none() && !names.contains(name.as_str()) => undefined = Some(name),
+                _ =>
+                {},
+            });
+            if let Some(name) = undefined
+            {
+                return Err(GrammarError::UndefinedRule {
+                    name :          name.clone(),
+                    referenced_by : rule.name.clone(),
+                });
+            }
+        }
+
+        Ok(Self {
+            rules,
+        })
+    }
+
+    pub fn rules(&self) -> &[Rule] { &self.rules }
+
+    pub fn rule(&self, name : &str) -> Option<&Rule> { self.rules.iter().find(|r| r.name == name) }
+
+    pub fn start(&self) -> &Rule { &self.rules[0] }
+}
+
+impl Node
+{
+    // Finds the first node, depth first, that matched the given rule - including this node
+    pub fn find(&self, rule : &str) -> Option<&Node>
+    {
+        if self.rule == rule
+        {
+            Some(self)
+        }
+        else
+        {
+            self.children.iter().find_map(|c| c.find(rule))
+        }
+    }
+}
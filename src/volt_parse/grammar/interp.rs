@@ -0,0 +1,226 @@
+use super::{
+    error::GrammarError,
+    expr::{Expr, Grammar, Node},
+};
+use crate::volt_parse::{
+    file_pos::FilePos,
+    parser::{PErr, POut, PRes, Parser, ParserInput},
+    span::{consumed, Span},
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+impl Grammar
+{
+    // Creates a parser that matches the named rule, producing a node for it and for every rule matched within it
+    pub fn parser<'a>(&self, rule : &str) -> Result<impl Parser<'a, Node>, GrammarError>
+    {
+        let start = self
+            .rules
+            .iter()
+            .position(|r| r.name == rule)
+            .ok_or_else(|| GrammarError::UnknownStartRule(rule.to_string()))?;
+        let grammar = Rc::new(self.clone());
+
+        Ok(move |ind : &ParserInput<'a>| -> POut<'a, Node> { run(&grammar, start, *ind, false) })
+    }
+
+    // Parses the entirety of `text` using the starting rule
+    pub fn parse<'a>(&self, text : &'a str) -> POut<'a, Node> { run(self, 0, ParserInput::new(text), true) }
+}
+
+fn run<'a>(grammar : &Grammar, start : usize, ind : ParserInput<'a>, whole : bool) -> POut<'a, Node>
+{
+    let interp = Interp::new(grammar);
+    let mut nodes = vec![];
+    match interp.rule(start, ind, &mut nodes)
+    {
+        Some(out) if !whole || out.text.is_empty() => Ok(PRes {
+            val :       nodes.pop().unwrap(),
+            pos :       out.pos,
+            remainder : out.text,
+        }),
+        Some(out) => Err(PErr {
+            pos : interp.farthest.get().max(out.pos),
+        }),
+        None => Err(PErr {
+            pos : interp.farthest.get(),
+        }),
+    }
+}
+
+struct Interp<'g>
+{
+    grammar :  &'g Grammar,
+    indices :  HashMap<&'g str, usize>,
+    // Where the furthest failure happened, as that is usually the most useful place to report an error
+    farthest : Cell<FilePos>,
+    // The rules being matched and the length of the text each began at, so that a rule calling itself without
+    // consuming anything fails rather than recursing until the stack overflows
+    active :   RefCell<HashSet<(usize, usize)>>,
+}
+
+impl<'g> Interp<'g>
+{
+    fn new(grammar : &'g Grammar) -> Self
+    {
+        Self {
+            grammar,
+            indices : grammar
+                .rules
+                .iter()
+                .enumerate()
+                .map(|(i, r)| (r.name.as_str(), i))
+                .collect(),
+            farthest : Cell::new(FilePos::default()),
+            active : RefCell::new(HashSet::new()),
+        }
+    }
+
+    fn fail<'a>(&self, ind : ParserInput<'a>) -> Option<ParserInput<'a>>
+    {
+        self.farthest.set(self.farthest.get().max(ind.pos));
+        None
+    }
+
+    fn advance<'a>(ind : ParserInput<'a>, len : usize) -> ParserInput<'a>
+    {
+        ParserInput {
+            text : &ind.text[len..],
+            pos :  ind.pos.advance(&ind.text[..len]),
+        }
+    }
+
+    fn rule<'a>(&self, index : usize, ind : ParserInput<'a>, nodes : &mut Vec<Node>) -> Option<ParserInput<'a>>
+    {
+        let rule = &self.grammar.rules[index];
+        let key = (index, ind.text.len());
+        if !self.active.borrow_mut().insert(key)
+        {
+            return self.fail(ind);
+        }
+        let mut children = vec![];
+        let out = self.eval(&rule.expr, ind, &mut children);
+        self.active.borrow_mut().remove(&key);
+        let out = out?;
+        nodes.push(Node {
+            rule : rule.name.clone(),
+            span : Span::new(ind.pos, out.pos),
+            text : consumed(ind.text, out.text).to_string(),
+            children,
+        });
+        Some(out)
+    }
+
+    fn eval<'a>(&self, expr : &Expr, ind : ParserInput<'a>, nodes : &mut Vec<Node>) -> Option<ParserInput<'a>>
+    {
+        match expr
+        {
+            Expr::Literal(lit) =>
+            {
+                if ind.text.starts_with(lit.as_str())
+                {
+                    Some(Self::advance(ind, lit.len()))
+                }
+                else
+                {
+                    self.fail(ind)
+                }
+            },
+            Expr::Class {
+                ranges,
+                negated,
+            } => match ind.text.chars().next()
+            {
+                Some(c) if ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)) != *negated =>
+                    Some(Self::advance(ind, c.len_utf8())),
+                _ => self.fail(ind),
+            },
+            Expr::Any => match ind.text.chars().next()
+            {
+                Some(c) => Some(Self::advance(ind, c.len_utf8())),
+                None => self.fail(ind),
+            },
+            Expr::Rule(name) => self.rule(self.indices[name.as_str()], ind, nodes),
+            Expr::Seq(es) =>
+            {
+                let mark = nodes.len();
+                let mut cur = ind;
+                for e in es
+                {
+                    match self.eval(e, cur, nodes)
+                    {
+                        Some(next) => cur = next,
+                        None =>
+                        {
+                            nodes.truncate(mark);
+                            return None;
+                        },
+                    }
+                }
+                Some(cur)
+            },
+            Expr::Choice(es) => es.iter().find_map(|e| {
+                let mark = nodes.len();
+                let out = self.eval(e, ind, nodes);
+                if out.is_none()
+                {
+                    nodes.truncate(mark);
+                }
+                out
+            }),
+            Expr::ZeroOrMore(e) => Some(self.repeat(e, ind, nodes)),
+            Expr::OneOrMore(e) =>
+            {
+                let first = self.eval(e, ind, nodes)?;
+                Some(self.repeat(e, first, nodes))
+            },
+            Expr::Optional(e) =>
+            {
+                let mark = nodes.len();
+                self.eval(e, ind, nodes).or_else(|| {
+                    nodes.truncate(mark);
+                    Some(ind)
+                })
+            },
+            Expr::And(e) => self.lookahead(e, ind).map(|_| ind),
+            Expr::Not(e) => match self.lookahead(e, ind)
+            {
+                Some(_) => self.fail(ind),
+                None => Some(ind),
+            },
+        }
+    }
+
+    // Failures within a predicate are expected, so they should not be reported as the furthest failure
+    fn lookahead<'a>(&self, e : &Expr, ind : ParserInput<'a>) -> Option<ParserInput<'a>>
+    {
+        let farthest = self.farthest.get();
+        let out = self.eval(e, ind, &mut vec![]);
+        self.farthest.set(farthest);
+        out
+    }
+
+    // Matches `e` as many times as possible, stopping early should it stop consuming input
+    fn repeat<'a>(&self, e : &Expr, ind : ParserInput<'a>, nodes : &mut Vec<Node>) -> ParserInput<'a>
+    {
+        let mut cur = ind;
+        loop
+        {
+            let mark = nodes.len();
+            match self.eval(e, cur, nodes)
+            {
+                Some(next) if next.text.len() < cur.text.len() => cur = next,
+                Some(_) => return cur,
+                None =>
+                {
+                    nodes.truncate(mark);
+                    return cur;
+                },
+            }
+        }
+    }
+}
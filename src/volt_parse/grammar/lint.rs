@@ -0,0 +1,301 @@
+use super::{
+    analysis::{complement, first, normalize, FirstSet},
+    expr::{Expr, Grammar},
+};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+};
+
+// A likely mistake within a grammar, found without parsing anything
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lint
+{
+    // An alternative that can never match, because an earlier alternative always matches first
+    ShadowedAlternative
+    {
+        rule :        String,
+        alternative : String,
+        shadowed_by : String,
+    },
+    // A repetition of something that may match nothing, which gives up at the first item that matches nothing
+    NullableRepetition
+    {
+        rule : String,
+        expr : String,
+    },
+    // A rule that may call itself without consuming anything, through each rule in `path`, which never terminates
+    // when built from combinators, and can never match through that path when interpreted
+    LeftRecursion
+    {
+        rule : String,
+        path : Vec<String>,
+    },
+    // A rule that cannot be reached from the starting rule
+    UnusedRule(String),
+}
+
+impl Display for Lint
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Lint::ShadowedAlternative {
+                rule,
+                alternative,
+                shadowed_by,
+            } => write!(
+                f,
+                "rule '{rule}': alternative `{alternative}` can never match, as `{shadowed_by}` always matches first"
+            ),
+            Lint::NullableRepetition {
+                rule,
+                expr,
+            } => write!(f, "rule '{rule}': `{expr}` repeats something that may match nothing"),
+            Lint::LeftRecursion {
+                rule,
+                path,
+            } => write!(f, "rule '{rule}' is left recursive: {}", path.join(" -> ")),
+            Lint::UnusedRule(rule) => write!(f, "rule '{rule}' is never used"),
+        }
+    }
+}
+
+// The characters that every match of an expression must begin with, one set per character
+struct Prefix
+{
+    chars :   Vec<Vec<(char, char)>>,
+    // Whether the expression always matches, consuming exactly one character from each set
+    fixed :   bool,
+    // Whether the expression always matches when the input begins with a character from each set
+    certain : bool,
+}
+
+impl Grammar
+{
+    // Checks the grammar for likely mistakes, returning them in the order the rules are defined
+    pub fn lint(&self) -> Vec<Lint>
+    {
+        let sets = self.first_sets();
+        let mut lints = vec![];
+
+        for rule in &self.rules
+        {
+            rule.expr.visit(&mut |e| match e
+            {
+                Expr::Choice(es) =>
+                {
+                    for (j, later) in es.iter().enumerate()
+                    {
+                        if let Some(earlier) = es[..j].iter().find(|earlier| self.shadows(earlier, later))
+                        {
+                            lints.push(Lint::ShadowedAlternative {
+                                rule :        rule.name.clone(),
+                                alternative : later.to_string(),
+                                shadowed_by : earlier.to_string(),
+                            });
+                        }
+                    }
+                },
+                Expr::ZeroOrMore(inner) | Expr::OneOrMore(inner) if first(inner, &sets).nullable =>
+                    lints.push(Lint::NullableRepetition {
+                        rule : rule.name.clone(),
+                        expr : e.to_string(),
+                    }),
+                _ =>
+                {},
+            });
+        }
+
+        let mut cycles = BTreeSet::new();
+        for rule in &self.rules
+        {
+            if let Some(path) = self.left_recursion(&rule.name, &sets)
+            {
+                // Rules within the same cycle are only reported once
+                let members = path.iter().cloned().collect::<BTreeSet<_>>();
+                if cycles.insert(members)
+                {
+                    lints.push(Lint::LeftRecursion {
+                        rule : rule.name.clone(),
+                        path,
+                    });
+                }
+            }
+        }
+
+        let reachable = self.reachable();
+        lints.extend(
+            self.rules
+                .iter()
+                .filter(|r| !reachable.contains(&r.name))
+                .map(|r| Lint::UnusedRule(r.name.clone())),
+        );
+        lints
+    }
+
+    // Whether `earlier` matches whenever `later` would, when tried first
+    fn shadows(&self, earlier : &Expr, later : &Expr) -> bool
+    {
+        if always_matches(earlier)
+        {
+            return true;
+        }
+        let earlier = self.prefix(earlier, &mut vec![]);
+        let later = self.prefix(later, &mut vec![]);
+        earlier.certain &&
+            earlier.chars.len() <= later.chars.len() &&
+            earlier.chars.iter().zip(&later.chars).all(|(e, l)| covers(e, l))
+    }
+
+    fn prefix(&self, expr : &Expr, rules : &mut Vec<String>) -> Prefix
+    {
+        match expr
+        {
+            Expr::Literal(lit) => Prefix {
+                chars :   lit.chars().map(|c| vec![(c, c)]).collect(),
+                fixed :   true,
+                certain : true,
+            },
+            Expr::Class {
+                ranges,
+                negated,
+            } => Prefix {
+                chars :   vec![if *negated
+                {
+                    complement(ranges)
+                }
+                else
+                {
+                    normalize(ranges.clone())
+                }],
+                fixed :   true,
+                certain : true,
+            },
+            Expr::Any => Prefix {
+                chars :   vec![FirstSet::any().ranges],
+                fixed :   true,
+                certain : true,
+            },
+            // Recursive rules are given up on, rather than followed forever
+            Expr::Rule(name) if !rules.contains(name) =>
+            {
+                rules.push(name.clone());
+                let prefix = self.prefix(&self.rule(name).unwrap().expr, rules);
+                rules.pop();
+                prefix
+            },
+            Expr::Seq(es) =>
+            {
+                let mut out = Prefix {
+                    chars :   vec![],
+                    fixed :   true,
+                    certain : true,
+                };
+                for (i, e) in es.iter().enumerate()
+                {
+                    let prefix = self.prefix(e, rules);
+                    out.chars.extend(prefix.chars);
+                    if !prefix.fixed
+                    {
+                        // Anything after this may still fail
+                        out.fixed = false;
+                        out.certain = prefix.certain && i == es.len() - 1;
+                        break;
+                    }
+                }
+                out
+            },
+            Expr::OneOrMore(e) => Prefix {
+                fixed : false,
+                ..self.prefix(e, rules)
+            },
+            _ => Prefix {
+                chars :   vec![],
+                fixed :   false,
+                certain : false,
+            },
+        }
+    }
+
+    // Finds a path of calls made before anything is consumed, from the rule back to itself
+    fn left_recursion(&self, rule : &str, sets : &HashMap<String, FirstSet>) -> Option<Vec<String>>
+    {
+        let mut path = vec![rule.to_string()];
+        let mut seen = BTreeSet::new();
+        self.find_left_call(rule, sets, &mut path, &mut seen).then_some(path)
+    }
+
+    fn find_left_call(
+        &self,
+        target : &str,
+        sets : &HashMap<String, FirstSet>,
+        path : &mut Vec<String>,
+        seen : &mut BTreeSet<String>,
+    ) -> bool
+    {
+        let current = path.last().unwrap().clone();
+        for callee in left_calls(&self.rule(&current).unwrap().expr, sets)
+        {
+            path.push(callee.clone());
+            if callee == target || (seen.insert(callee) && self.find_left_call(target, sets, path, seen))
+            {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+}
+
+// Whether the expression succeeds on any input
+fn always_matches(expr : &Expr) -> bool
+{
+    match expr
+    {
+        Expr::ZeroOrMore(_) | Expr::Optional(_) => true,
+        Expr::Literal(lit) => lit.is_empty(),
+        Expr::Seq(es) => es.iter().all(always_matches),
+        Expr::Choice(es) => es.iter().any(always_matches),
+        _ => false,
+    }
+}
+
+// Whether every range within `inner` is within `outer`, both being normalized
+fn covers(outer : &[(char, char)], inner : &[(char, char)]) -> bool
+{
+    inner
+        .iter()
+        .all(|(ilo, ihi)| outer.iter().any(|(olo, ohi)| olo <= ilo && ihi <= ohi))
+}
+
+// The rules that may be called by the expression before it consumes anything
+fn left_calls(expr : &Expr, sets : &HashMap<String, FirstSet>) -> Vec<String>
+{
+    match expr
+    {
+        Expr::Rule(name) => vec![name.clone()],
+        Expr::Seq(es) =>
+        {
+            let mut out = vec![];
+            for e in es
+            {
+                out.extend(left_calls(e, sets));
+                if !first(e, sets).nullable
+                {
+                    break;
+                }
+            }
+            out
+        },
+        Expr::Choice(es) => es.iter().flat_map(|e| left_calls(e, sets)).collect(),
+        Expr::ZeroOrMore(e) | Expr::OneOrMore(e) | Expr::Optional(e) | Expr::And(e) | Expr::Not(e) =>
+            left_calls(e, sets),
+        Expr::Literal(_) |
+        Expr::Class {
+            ..
+        } |
+        Expr::Any => vec![],
+    }
+}
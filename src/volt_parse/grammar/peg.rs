@@ -0,0 +1,217 @@
+use super::{
+    error::GrammarError,
+    expr::{Expr, Grammar, Rule},
+};
+use crate::prelude::*;
+
+// The textual PEG syntax understood by `Grammar::from_peg`:
+//
+//   # Comments run until the end of the line
+//   Rule   <- Other "literal" 'literal' [a-z_] [^\n] . (Group / Choice)
+//   Suffix <- a* b+ c? &lookahead !negative_lookahead ;
+//
+// Rules may also be defined with `=` or `::=`, and alternatives may also be separated with `|`. Literals and classes
+// understand the escapes \n \r \t \0 \\ \' \" \[ \] \- \^ and \u{XXXX}.
+
+impl Grammar
+{
+    pub fn from_peg(text : &str) -> Result<Self, GrammarError>
+    {
+        let rules = peg()(&ParserInput::new(text)).map_err(GrammarError::Syntax)?.val;
+        Grammar::new(rules)
+    }
+}
+
+// Parses an entire PEG grammar into its rules
+pub fn peg<'a>() -> impl Parser<'a, Vec<Rule>> { all(then(spacing(), one_or_many(definition()), take_right)) }
+
+fn spacing<'a>() -> impl Parser<'a, Vec<CstToken>> { trivia(Trivia::whitespace().with_line_comment("#")) }
+
+// Skips any spacing after `p`
+fn tok<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parser<'a, DatT>
+{
+    lexeme_with(Trivia::whitespace().with_line_comment("#"), p)
+}
+
+fn identifier<'a>() -> impl Parser<'a, String>
+{
+    then(
+        read_char_f(|c| c.is_ascii_alphabetic() || c == '_'),
+        none_or_many(read_char_f(|c| c.is_ascii_alphanumeric() || c == '_')),
+        smcomb(|first, rest : Vec<char>| std::iter::once(first).chain(rest).collect()),
+    )
+}
+
+fn arrow<'a>() -> impl Parser<'a, String> { or(or(keyword("<-"), keyword("::=")), keyword("=")) }
+
+fn escape<'a>() -> impl Parser<'a, char>
+{
+    let unicode = mod_out(
+        then(
+            keyword("u{"),
+            then(
+                one_or_many(read_char_f(|c| c.is_ascii_hexdigit())),
+                char_single('}'),
+                take_left,
+            ),
+            take_right,
+        ),
+        |res : PRes<'a, Vec<char>>| {
+            let digits = res.val.iter().collect::<String>();
+            match u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32)
+            {
+                Some(c) => Ok(res.with_val(c)),
+                None => Err(PErr {
+                    pos : res.pos
+                }),
+            }
+        },
+    );
+    let simple = mod_val(read_char_f(|c| "nrt0\\'\"[]-^".contains(c)), |c| match c
+    {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '0' => '\0',
+        c => c,
+    });
+    then(char_single('\\'), or(unicode, simple), take_right)
+}
+
+fn literal<'a>() -> impl Parser<'a, Expr>
+{
+    let quoted = |q : char| {
+        then(
+            char_single(q),
+            then(
+                none_or_many(or(escape(), read_char_f(move |c| c != q && c != '\\'))),
+                char_single(q),
+                take_left,
+            ),
+            take_right,
+        )
+    };
+    mod_val(or(quoted('"'), quoted('\'')), |cs| {
+        Expr::Literal(cs.into_iter().collect())
+    })
+}
+
+fn class<'a>() -> impl Parser<'a, Expr>
+{
+    let class_char = || or(escape(), read_char_f(|c| c != ']' && c != '\\'));
+    let range = then(
+        class_char(),
+        maybe(then(char_single('-'), class_char(), take_right)),
+        smcomb(|lo, hi : Option<char>| (lo, hi.unwrap_or(lo))),
+    );
+    mod_val(
+        then(
+            char_single('['),
+            then(
+                maybe(char_single('^')),
+                then(none_or_many(range), char_single(']'), take_left),
+                left_right,
+            ),
+            take_right,
+        ),
+        |(negated, ranges)| Expr::Class {
+            ranges,
+            negated : negated.is_some(),
+        },
+    )
+}
+
+fn primary<'a>() -> impl Parser<'a, Expr>
+{
+    // A reference to another rule, which must not be the start of the next definition
+    let rule_ref = mod_val(
+        tok(then(
+            identifier(),
+            not(no_consume(then(spacing(), arrow(), take_right)), String::new),
+            take_left,
+        )),
+        Expr::Rule,
+    );
+    let group = then(
+        tok(char_single('(')),
+        then(expression(), tok(char_single(')')), take_left),
+        take_right,
+    );
+    let any = mod_val(tok(char_single('.')), |_| Expr::Any);
+    or(or(or(rule_ref, group), or(tok(literal()), tok(class()))), any)
+}
+
+fn suffix<'a>() -> impl Parser<'a, Expr>
+{
+    then(
+        primary(),
+        maybe(tok(char_in_str("*+?"))),
+        smcomb(|e, op : Option<char>| match op
+        {
+            Some('*') => Expr::ZeroOrMore(Box::new(e)),
+            Some('+') => Expr::OneOrMore(Box::new(e)),
+            Some(_) => Expr::Optional(Box::new(e)),
+            None => e,
+        }),
+    )
+}
+
+fn prefix<'a>() -> impl Parser<'a, Expr>
+{
+    then(
+        maybe(tok(char_in_str("&!"))),
+        suffix(),
+        smcomb(|op : Option<char>, e| match op
+        {
+            Some('&') => Expr::And(Box::new(e)),
+            Some(_) => Expr::Not(Box::new(e)),
+            None => e,
+        }),
+    )
+}
+
+fn sequence<'a>() -> impl Parser<'a, Expr>
+{
+    mod_val(none_or_many(prefix()), |mut es| {
+        if es.len() == 1
+        {
+            es.remove(0)
+        }
+        else
+        {
+            Expr::Seq(es)
+        }
+    })
+}
+
+fn expression<'a>() -> impl Parser<'a, Expr>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Expr> {
+        mod_val(sep_by1(sequence(), tok(char_in_str("/|"))), |mut es| {
+            if es.len() == 1
+            {
+                es.remove(0)
+            }
+            else
+            {
+                Expr::Choice(es)
+            }
+        })(ind)
+    }
+}
+
+fn definition<'a>() -> impl Parser<'a, Rule>
+{
+    then(
+        tok(identifier()),
+        then(
+            tok(arrow()),
+            then(expression(), maybe(tok(char_single(';'))), take_left),
+            take_right,
+        ),
+        smcomb(|name, expr| Rule {
+            name,
+            expr,
+        }),
+    )
+}
@@ -0,0 +1,319 @@
+use super::expr::{Expr, Grammar, Rule};
+use std::fmt::Write;
+
+// Railroad (syntax) diagrams drawn as SVG, with no external resources so they can be viewed offline.
+//
+// Every part of a diagram is laid out around a horizontal line that enters on its left and leaves on its right, `up`
+// and `down` being how far the part reaches above and below that line.
+
+const ARC : i32 = 10;
+const GAP : i32 = 10;
+const PAD : i32 = 20;
+const BOX : i32 = 11;
+const LABEL : i32 = 14;
+
+const STYLE : &str = "path { fill: none; stroke: #333; stroke-width: 2 }
+rect { fill: #ffc; stroke: #333; stroke-width: 2 }
+.nonterminal rect { fill: #cef }
+.group rect { fill: none; stroke: #999; stroke-dasharray: 4 }
+text { font: 13px monospace; text-anchor: middle }
+.group text { font-size: 11px; text-anchor: start; fill: #666 }
+";
+
+enum Item
+{
+    Terminal(String),
+    NonTerminal(String),
+    Skip,
+    Seq(Vec<Item>),
+    // The first item is drawn on the line, the rest beneath it
+    Choice(Vec<Item>),
+    // The item may be repeated by following the line back underneath it
+    Loop(Box<Item>),
+    // A dashed box around the item, for matches that do not consume anything
+    Group(&'static str, Box<Item>),
+}
+
+struct Size
+{
+    width : i32,
+    up :    i32,
+    down :  i32,
+}
+
+impl Item
+{
+    fn new(expr : &Expr) -> Self
+    {
+        match expr
+        {
+            Expr::Literal(_) |
+            Expr::Class {
+                ..
+            } => Item::Terminal(expr.to_string()),
+            Expr::Any => Item::Terminal(String::from("any character")),
+            Expr::Rule(name) => Item::NonTerminal(name.clone()),
+            Expr::Seq(es) if es.is_empty() => Item::Skip,
+            Expr::Seq(es) | Expr::Choice(es) if es.len() == 1 => Item::new(&es[0]),
+            Expr::Seq(es) => Item::Seq(es.iter().map(Item::new).collect()),
+            Expr::Choice(es) => Item::Choice(es.iter().map(Item::new).collect()),
+            Expr::ZeroOrMore(e) => Item::Choice(vec![Item::Loop(Box::new(Item::new(e))), Item::Skip]),
+            Expr::OneOrMore(e) => Item::Loop(Box::new(Item::new(e))),
+            Expr::Optional(e) => Item::Choice(vec![Item::new(e), Item::Skip]),
+            Expr::And(e) => Item::Group("followed by", Box::new(Item::new(e))),
+            Expr::Not(e) => Item::Group("not followed by", Box::new(Item::new(e))),
+        }
+    }
+
+    fn size(&self) -> Size
+    {
+        match self
+        {
+            Item::Terminal(text) | Item::NonTerminal(text) => Size {
+                width : text_width(text),
+                up :    BOX,
+                down :  BOX,
+            },
+            Item::Skip => Size {
+                width : 0,
+                up :    0,
+                down :  0,
+            },
+            Item::Seq(items) =>
+            {
+                let sizes = items.iter().map(Item::size).collect::<Vec<_>>();
+                Size {
+                    width : sizes.iter().map(|s| s.width).sum::<i32>() + GAP * (sizes.len() as i32 - 1),
+                    up :    sizes.iter().map(|s| s.up).max().unwrap_or(0),
+                    down :  sizes.iter().map(|s| s.down).max().unwrap_or(0),
+                }
+            },
+            Item::Choice(items) =>
+            {
+                let sizes = items.iter().map(Item::size).collect::<Vec<_>>();
+                let offsets = branch_offsets(&sizes);
+                Size {
+                    width : sizes.iter().map(|s| s.width).max().unwrap_or(0) + 4 * ARC,
+                    up :    sizes[0].up,
+                    down :  offsets.last().unwrap() + sizes.last().unwrap().down,
+                }
+            },
+            Item::Loop(item) =>
+            {
+                let size = item.size();
+                Size {
+                    width : size.width + 4 * ARC,
+                    up :    size.up,
+                    down :  loop_offset(&size),
+                }
+            },
+            Item::Group(_, item) =>
+            {
+                let size = item.size();
+                Size {
+                    width : size.width + 2 * GAP,
+                    up :    size.up + GAP + LABEL,
+                    down :  size.down + GAP,
+                }
+            },
+        }
+    }
+
+    // Draws the item with its line entering at `x`, `y`
+    fn draw(&self, out : &mut String, x : i32, y : i32)
+    {
+        match self
+        {
+            Item::Terminal(text) => draw_box(out, "terminal", text, x, y, BOX),
+            Item::NonTerminal(name) =>
+            {
+                let _ = write!(out, "<a href=\"#rule-{}\">", escape(name));
+                draw_box(out, "nonterminal", name, x, y, 0);
+                out.push_str("</a>\n");
+            },
+            Item::Skip =>
+            {},
+            Item::Seq(items) =>
+            {
+                let mut x = x;
+                for (i, item) in items.iter().enumerate()
+                {
+                    if i > 0
+                    {
+                        line(out, x, y, GAP);
+                        x += GAP;
+                    }
+                    item.draw(out, x, y);
+                    x += item.size().width;
+                }
+            },
+            Item::Choice(items) =>
+            {
+                let sizes = items.iter().map(Item::size).collect::<Vec<_>>();
+                let inner = sizes.iter().map(|s| s.width).max().unwrap_or(0);
+
+                line(out, x, y, 2 * ARC);
+                items[0].draw(out, x + 2 * ARC, y);
+                line(out, x + 2 * ARC + sizes[0].width, y, inner - sizes[0].width + 2 * ARC);
+
+                for ((item, size), offset) in items.iter().zip(&sizes).skip(1).zip(branch_offsets(&sizes))
+                {
+                    let drop = offset - 2 * ARC;
+                    let _ = writeln!(
+                        out,
+                        "<path d=\"M{x} {y} a{ARC} {ARC} 0 0 1 {ARC} {ARC} v{drop} a{ARC} {ARC} 0 0 0 {ARC} {ARC}\"/>"
+                    );
+                    item.draw(out, x + 2 * ARC, y + offset);
+                    let _ = writeln!(
+                        out,
+                        "<path d=\"M{} {} h{} a{ARC} {ARC} 0 0 0 {ARC} -{ARC} v-{drop} a{ARC} {ARC} 0 0 1 {ARC} \
+                         -{ARC}\"/>",
+                        x + 2 * ARC + size.width,
+                        y + offset,
+                        inner - size.width,
+                    );
+                }
+            },
+            Item::Loop(item) =>
+            {
+                let size = item.size();
+                let rise = loop_offset(&size) - 2 * ARC;
+
+                line(out, x, y, 2 * ARC);
+                item.draw(out, x + 2 * ARC, y);
+                line(out, x + 2 * ARC + size.width, y, 2 * ARC);
+                let _ = writeln!(
+                    out,
+                    "<path d=\"M{} {y} a{ARC} {ARC} 0 0 1 {ARC} {ARC} v{rise} a{ARC} {ARC} 0 0 1 -{ARC} {ARC} h-{} \
+                     a{ARC} {ARC} 0 0 1 -{ARC} -{ARC} v-{rise} a{ARC} {ARC} 0 0 1 {ARC} -{ARC}\"/>",
+                    x + 2 * ARC + size.width,
+                    size.width,
+                );
+            },
+            Item::Group(label, item) =>
+            {
+                let size = item.size();
+                let top = y - size.up - GAP;
+                let _ = writeln!(
+                    out,
+                    "<g class=\"group\"><rect x=\"{x}\" y=\"{top}\" width=\"{}\" height=\"{}\" rx=\"5\"/><text \
+                     x=\"{x}\" y=\"{}\">{label}</text></g>",
+                    size.width + 2 * GAP,
+                    size.up + size.down + 2 * GAP,
+                    top - 4,
+                );
+                line(out, x, y, GAP);
+                item.draw(out, x + GAP, y);
+                line(out, x + GAP + size.width, y, GAP);
+            },
+        }
+    }
+}
+
+// How far beneath the line each branch after the first is drawn
+fn branch_offsets(sizes : &[Size]) -> Vec<i32>
+{
+    let mut offsets = vec![];
+    let mut bottom = sizes[0].down;
+    for size in &sizes[1..]
+    {
+        let offset = (bottom + GAP + size.up).max(2 * ARC);
+        offsets.push(offset);
+        bottom = offset + size.down;
+    }
+    offsets
+}
+
+// How far beneath the line the path back to the start of a loop is drawn
+fn loop_offset(size : &Size) -> i32 { (size.down + GAP).max(2 * ARC) }
+
+// Text is drawn in a monospace font, so its width can be estimated from its length
+fn text_width(text : &str) -> i32 { text.chars().count() as i32 * 8 + 2 * GAP }
+
+fn line(out : &mut String, x : i32, y : i32, len : i32)
+{
+    if len > 0
+    {
+        let _ = writeln!(out, "<path d=\"M{x} {y} h{len}\"/>");
+    }
+}
+
+fn draw_box(out : &mut String, class : &str, text : &str, x : i32, y : i32, radius : i32)
+{
+    let width = text_width(text);
+    let _ = writeln!(
+        out,
+        "<g class=\"{class}\"><rect x=\"{x}\" y=\"{}\" width=\"{width}\" height=\"{}\" rx=\"{radius}\"/><text \
+         x=\"{}\" y=\"{}\">{}</text></g>",
+        y - BOX,
+        2 * BOX,
+        x + width / 2,
+        y + 4,
+        escape(text),
+    );
+}
+
+fn escape(text : &str) -> String
+{
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn svg(rule : &Rule, style : bool) -> String
+{
+    let item = Item::new(&rule.expr);
+    let size = item.size();
+    let width = size.width + 2 * PAD + 2 * GAP;
+    let height = size.up + size.down + 2 * PAD;
+    let y = PAD + size.up;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"railroad\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">"
+    );
+    if style
+    {
+        let _ = writeln!(out, "<style>\n{STYLE}</style>");
+    }
+    // The bars marking the start and end of the rule
+    let _ = writeln!(out, "<path d=\"M{PAD} {} v{} m0 -{ARC} h{GAP}\"/>", y - ARC, 2 * ARC);
+    item.draw(&mut out, PAD + GAP, y);
+    let _ = writeln!(
+        out,
+        "<path d=\"M{} {y} h{GAP} m0 -{ARC} v{}\"/>",
+        width - PAD - GAP,
+        2 * ARC
+    );
+    out.push_str("</svg>\n");
+    out
+}
+
+impl Rule
+{
+    // A standalone SVG railroad diagram of this rule
+    pub fn to_svg(&self) -> String { svg(self, true) }
+}
+
+impl Grammar
+{
+    // A standalone HTML page with a railroad diagram of every rule, where rule names link to their diagrams
+    pub fn to_html(&self) -> String
+    {
+        let mut out =
+            String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Grammar</title>\n");
+        let _ = writeln!(out, "<style>\n{STYLE}</style>\n</head>\n<body>");
+        for rule in &self.rules
+        {
+            let name = escape(&rule.name);
+            let _ = writeln!(out, "<h2 id=\"rule-{name}\">{name}</h2>");
+            let _ = writeln!(out, "<pre>{}</pre>", escape(&rule.to_string()));
+            out.push_str(&svg(rule, false));
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
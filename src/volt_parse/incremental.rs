@@ -0,0 +1,256 @@
+use super::{
+    cst::{CstElement, CstNode},
+    file_pos::FilePos,
+    parser::{PErr, POut, ParserInput},
+};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    ops::Range,
+};
+
+// Replaces the text within `range` (in bytes) with `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit
+{
+    pub range : Range<usize>,
+    pub text :  String,
+}
+
+impl Edit
+{
+    pub fn new(range : Range<usize>, text : &str) -> Self
+    {
+        Self {
+            range,
+            text : text.to_string(),
+        }
+    }
+}
+
+// Why an edit could not be applied to the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError
+{
+    // The range ends before it starts
+    Reversed(Range<usize>),
+    // This end of the range is past the end of the text
+    OutOfBounds(usize),
+    // This end of the range is within a character
+    NotCharBoundary(usize),
+}
+
+impl Display for EditError
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            EditError::Reversed(range) => write!(f, "edit range {}..{} ends before it starts", range.start, range.end),
+            EditError::OutOfBounds(offset) => write!(f, "edit offset {offset} is past the end of the text"),
+            EditError::NotCharBoundary(offset) => write!(f, "edit offset {offset} is within a character"),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+// The nodes that may be reused while reparsing, keyed by kind and byte offset
+struct Memo
+{
+    doc_len : usize,
+    nodes :   HashMap<(String, usize), CstNode>,
+    reused :  usize,
+}
+
+thread_local! {
+    static MEMO : RefCell<Option<Memo>> = const { RefCell::new(None) };
+}
+
+// Called by `node` before it parses anything, returning the node previously parsed at this position if it is still
+// valid
+pub(crate) fn reuse<'a>(kind : &str, ind : &ParserInput<'a>) -> Option<CstNode>
+{
+    MEMO.with(|memo| {
+        let mut memo = memo.borrow_mut();
+        let memo = memo.as_mut()?;
+        let offset = memo.doc_len.checked_sub(ind.text.len())?;
+        let node = memo.nodes.get(&(kind.to_string(), offset))?;
+        if node.len() > ind.text.len()
+        {
+            return None;
+        }
+        memo.reused += 1;
+        let mut node = node.clone();
+        node.rebase(ind.pos);
+        Some(node)
+    })
+}
+
+// Keeps a document and its syntax tree up to date as the document is edited, only reparsing the nodes affected by each
+// edit.
+//
+// Parsers only ever look forwards, so a node starting after an edit is unaffected by it. A node ending before an edit
+// is unaffected so long as it did not look further ahead than its own end, by up to `lookahead` bytes, into the
+// edited text. Each node kind must always be produced by the same parser.
+pub struct IncrementalParser<F>
+where
+    F : for<'a> Fn(&ParserInput<'a>) -> POut<'a, CstNode>,
+{
+    parser :    F,
+    text :      String,
+    tree :      Result<CstNode, PErr>,
+    lookahead : usize,
+    reused :    usize,
+}
+
+impl<F> IncrementalParser<F>
+where
+    F : for<'a> Fn(&ParserInput<'a>) -> POut<'a, CstNode>,
+{
+    // Parses `text` from scratch
+    pub fn new(parser : F, text : &str) -> Self
+    {
+        let tree = parse_with(&parser, text, HashMap::new()).0;
+        Self {
+            parser,
+            text : text.to_string(),
+            tree,
+            lookahead : 1,
+            reused : 0,
+        }
+    }
+
+    // How many bytes past its end a node may have looked at, defaults to 1 which suits greedy repetition
+    pub fn with_lookahead(mut self, lookahead : usize) -> Self
+    {
+        self.lookahead = lookahead;
+        self
+    }
+
+    pub fn text(&self) -> &str { &self.text }
+
+    pub fn tree(&self) -> &Result<CstNode, PErr> { &self.tree }
+
+    // How many nodes were reused rather than reparsed by the last edit
+    pub fn reused(&self) -> usize { self.reused }
+
+    // Applies the edit to the text and reparses it, reusing every node that the edit could not have affected. An edit
+    // whose range is not within the text, or not on character boundaries, is rejected and leaves everything as it was.
+    pub fn edit(&mut self, edit : &Edit) -> Result<&Result<CstNode, PErr>, EditError>
+    {
+        let range = edit.range.clone();
+        if range.end < range.start
+        {
+            return Err(EditError::Reversed(range));
+        }
+        for offset in [range.start, range.end]
+        {
+            if offset > self.text.len()
+            {
+                return Err(EditError::OutOfBounds(offset));
+            }
+            if !self.text.is_char_boundary(offset)
+            {
+                return Err(EditError::NotCharBoundary(offset));
+            }
+        }
+
+        let old_len = range.end - range.start;
+        let mut nodes = HashMap::new();
+        if let Ok(tree) = &self.tree
+        {
+            // A node may be nested within another of the same kind at the same offset, such as `e` within `e + e`,
+            // where only the outer node can be reused, as it is the one parsed first. The outer node is visited first.
+            let mut seen = HashSet::new();
+            collect(tree, 0, &mut |node, offset| {
+                let end = offset + node.len();
+                let key = if end + self.lookahead <= range.start
+                {
+                    Some(offset)
+                }
+                else if offset >= range.end
+                {
+                    Some(offset - old_len + edit.text.len())
+                }
+                else
+                {
+                    None
+                };
+                if seen.insert((node.kind.clone(), offset))
+                {
+                    if let Some(key) = key
+                    {
+                        nodes.insert((node.kind.clone(), key), node.clone());
+                    }
+                }
+            });
+        }
+
+        self.text.replace_range(range, &edit.text);
+        let (tree, reused) = parse_with(&self.parser, &self.text, nodes);
+        self.tree = tree;
+        self.reused = reused;
+        Ok(&self.tree)
+    }
+}
+
+fn parse_with<F>(parser : &F, text : &str, nodes : HashMap<(String, usize), CstNode>) -> (Result<CstNode, PErr>, usize)
+where
+    F : for<'a> Fn(&ParserInput<'a>) -> POut<'a, CstNode>,
+{
+    MEMO.with(|memo| {
+        *memo.borrow_mut() = Some(Memo {
+            doc_len : text.len(),
+            nodes,
+            reused : 0,
+        })
+    });
+    let tree = parser(&ParserInput::new(text)).map(|res| res.val);
+    let reused = MEMO.with(|memo| memo.borrow_mut().take().map(|m| m.reused).unwrap_or(0));
+    (tree, reused)
+}
+
+// Calls `f` with every node in the tree along with its byte offset
+fn collect(node : &CstNode, offset : usize, f : &mut impl FnMut(&CstNode, usize))
+{
+    f(node, offset);
+    let mut offset = offset;
+    for child in &node.children
+    {
+        if let CstElement::Node(n) = child
+        {
+            collect(n, offset, f);
+        }
+        offset += child.len();
+    }
+}
+
+impl CstNode
+{
+    // Moves this node to start at `start`, recalculating the spans of everything within it
+    pub fn rebase(&mut self, start : FilePos)
+    {
+        let mut pos = start;
+        for child in &mut self.children
+        {
+            match child
+            {
+                CstElement::Node(n) =>
+                {
+                    n.rebase(pos);
+                    pos = n.span.end;
+                },
+                CstElement::Token(t) =>
+                {
+                    t.span.start = pos;
+                    pos = pos.advance(&t.text);
+                    t.span.end = pos;
+                },
+            }
+        }
+        self.span.start = start;
+        self.span.end = pos;
+    }
+}
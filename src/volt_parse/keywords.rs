@@ -0,0 +1,147 @@
+use super::parser::{PErr, POut, PRes, PResData, Parser, ParserInput};
+use std::rc::Rc;
+
+// A set of keywords, each with an associated value, that can be searched for the longest keyword that the text begins
+// with in a single pass over the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordTrie<T>
+{
+    nodes :  Vec<TrieNode>,
+    values : Vec<T>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct TrieNode
+{
+    // Sorted by byte
+    children : Vec<(u8, usize)>,
+    // The index of the value of the keyword ending here
+    value :    Option<usize>,
+}
+
+impl<T> Default for KeywordTrie<T>
+{
+    fn default() -> Self
+    {
+        Self {
+            nodes :  vec![TrieNode::default()],
+            values : vec![],
+        }
+    }
+}
+
+impl<T> KeywordTrie<T>
+{
+    pub fn new() -> Self { Self::default() }
+
+    // Adds the keyword, replacing its value if it was already added
+    pub fn insert(&mut self, word : &str, val : T)
+    {
+        let mut node = 0;
+        for b in word.bytes()
+        {
+            node = match self.nodes[node].children.binary_search_by_key(&b, |(c, _)| *c)
+            {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) =>
+                {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(i, (b, child));
+                    child
+                },
+            };
+        }
+        match self.nodes[node].value
+        {
+            Some(i) => self.values[i] = val,
+            None =>
+            {
+                self.values.push(val);
+                self.nodes[node].value = Some(self.values.len() - 1);
+            },
+        }
+    }
+
+    // The length in bytes and value of the longest keyword that `text` begins with
+    pub fn longest_match(&self, text : &str) -> Option<(usize, &T)>
+    {
+        let mut node = &self.nodes[0];
+        let mut found = node.value.map(|v| (0, v));
+        for (i, b) in text.bytes().enumerate()
+        {
+            match node.children.binary_search_by_key(&b, |(c, _)| *c)
+            {
+                Ok(child) => node = &self.nodes[node.children[child].1],
+                Err(_) => break,
+            }
+            if let Some(v) = node.value
+            {
+                found = Some((i + 1, v));
+            }
+        }
+        found.map(|(len, v)| (len, &self.values[v]))
+    }
+}
+
+impl<'w, T> FromIterator<(&'w str, T)> for KeywordTrie<T>
+{
+    fn from_iter<I : IntoIterator<Item = (&'w str, T)>>(iter : I) -> Self
+    {
+        let mut trie = Self::new();
+        iter.into_iter().for_each(|(word, val)| trie.insert(word, val));
+        trie
+    }
+}
+
+// Matches the longest of the keywords, regardless of the order they are given in, returning the keyword matched
+#[inline]
+pub fn one_of_keywords<'a>(words : &[&str]) -> impl Parser<'a, String>
+{
+    let trie = Rc::new(words.iter().map(|w| (*w, ())).collect::<KeywordTrie<()>>());
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        trie_match(&trie, ind).map(|(res, _)| PRes {
+            val :       String::from(res.val),
+            pos :       res.pos,
+            remainder : res.remainder,
+        })
+    }
+}
+
+// Matches the longest of the keywords, regardless of the order they are given in, returning the value associated with
+// the keyword matched
+#[inline]
+pub fn one_of_keywords_val<'a, DatT : PResData>(words : &[(&str, DatT)]) -> impl Parser<'a, DatT>
+{
+    let trie = Rc::new(words.iter().cloned().collect::<KeywordTrie<DatT>>());
+    move |ind : &ParserInput<'a>| -> POut<'a, DatT> {
+        trie_match(&trie, ind).map(|(res, val)| PRes {
+            val :       val.clone(),
+            pos :       res.pos,
+            remainder : res.remainder,
+        })
+    }
+}
+
+fn trie_match<'a, 't, T>(trie : &'t KeywordTrie<T>, ind : &ParserInput<'a>)
+    -> Result<(PRes<'a, &'a str>, &'t T), PErr>
+{
+    match trie.longest_match(ind.text)
+    {
+        Some((len, val)) =>
+        {
+            let matched = &ind.text[..len];
+            Ok((
+                PRes {
+                    val :       matched,
+                    pos :       ind.pos.advance(matched),
+                    remainder : &ind.text[len..],
+                },
+                val,
+            ))
+        },
+        None => Err(PErr {
+            pos : ind.pos
+        }),
+    }
+}
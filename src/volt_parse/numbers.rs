@@ -0,0 +1,283 @@
+use super::parser::{PErr, POut, PRes, PResData, Parser, ParserInput};
+use std::{fmt::Debug, num::IntErrorKind, str::FromStr};
+
+// The integer types that can be parsed
+pub trait Integer: PResData + Copy
+{
+    const SIGNED : bool;
+
+    fn from_str_radix(text : &str, radix : u32) -> Result<Self, std::num::ParseIntError>;
+}
+
+macro_rules! impl_integer {
+    ($signed:expr, $($t:ty),*) => {
+        $(
+            impl Integer for $t
+            {
+                const SIGNED : bool = $signed;
+
+                fn from_str_radix(text : &str, radix : u32) -> Result<Self, std::num::ParseIntError>
+                {
+                    <$t>::from_str_radix(text, radix)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(true, i8, i16, i32, i64, i128, isize);
+impl_integer!(false, u8, u16, u32, u64, u128, usize);
+
+// Why an integer literal could not be represented by its type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntError
+{
+    TooLarge,
+    TooSmall,
+}
+
+// How integer literals are written
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IntFormat
+{
+    // Whether `0x`, `0o` and `0b` prefixes are read as hexadecimal, octal and binary
+    pub prefixes :  bool,
+    // A character that may separate digits, such as the `_` within `1_000`
+    pub separator : Option<char>,
+}
+
+impl IntFormat
+{
+    pub fn with_prefixes(mut self) -> Self
+    {
+        self.prefixes = true;
+        self
+    }
+
+    pub fn with_separator(mut self, separator : char) -> Self
+    {
+        self.separator = Some(separator);
+        self
+    }
+}
+
+// A float that can be used as parsed data, which floats themselves cannot as they are not `Eq`. Floats are equal when
+// their bits are, so NaN equals itself while 0.0 and -0.0 are not equal.
+#[derive(Debug, Clone, Copy)]
+pub struct Float<T>(pub T);
+
+// The float types that can be parsed
+pub trait FloatType: Debug + Copy + FromStr
+{
+    fn to_bits_u64(self) -> u64;
+}
+
+impl FloatType for f32
+{
+    fn to_bits_u64(self) -> u64 { self.to_bits() as u64 }
+}
+
+impl FloatType for f64
+{
+    fn to_bits_u64(self) -> u64 { self.to_bits() }
+}
+
+impl<T : FloatType> PartialEq for Float<T>
+{
+    fn eq(&self, other : &Self) -> bool { self.0.to_bits_u64() == other.0.to_bits_u64() }
+}
+
+impl<T : FloatType> Eq for Float<T> {}
+
+// How float literals are written
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FloatFormat
+{
+    // A character that may separate digits, such as the `_` within `1_000.5`
+    pub separator : Option<char>,
+    // Whether `inf`, `infinity` and `nan` are read, ignoring case
+    pub special :   bool,
+}
+
+impl FloatFormat
+{
+    pub fn with_separator(mut self, separator : char) -> Self
+    {
+        self.separator = Some(separator);
+        self
+    }
+
+    pub fn with_special(mut self) -> Self
+    {
+        self.special = true;
+        self
+    }
+}
+
+// A decimal integer, with a sign if the type is signed, failing if it does not fit within the type
+#[inline]
+pub fn integer<'a, T : Integer>() -> impl Parser<'a, T> { integer_with(IntFormat::default()) }
+
+#[inline]
+pub fn integer_with<'a, T : Integer>(format : IntFormat) -> impl Parser<'a, T>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, T> {
+        let res = integer_checked::<T>(format)(ind)?;
+        match res.val
+        {
+            Ok(val) => Ok(res.with_val(val)),
+            Err(_) => Err(PErr {
+                pos : ind.pos
+            }),
+        }
+    }
+}
+
+// Like `integer_with`, though an integer that does not fit within the type is still read, giving the reason instead
+#[inline]
+pub fn integer_checked<'a, T : Integer>(format : IntFormat) -> impl Parser<'a, Result<T, IntError>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Result<T, IntError>> {
+        let text = ind.text;
+        let mut digits = String::new();
+        let mut i = 0;
+        if text.starts_with('+') || (T::SIGNED && text.starts_with('-'))
+        {
+            digits.push_str(&text[..1]);
+            i = 1;
+        }
+
+        let mut radix = 10;
+        if format.prefixes
+        {
+            let prefix_radix = match text.get(i..i + 2)
+            {
+                Some("0x" | "0X") => 16,
+                Some("0o" | "0O") => 8,
+                Some("0b" | "0B") => 2,
+                _ => 10,
+            };
+            // A prefix without any digits after it is just a zero
+            if prefix_radix != 10 && text[i + 2..].chars().next().is_some_and(|c| c.is_digit(prefix_radix))
+            {
+                radix = prefix_radix;
+                i += 2;
+            }
+        }
+
+        let end = scan_digits(text, i, radix, format.separator, &mut digits);
+        if end == i
+        {
+            return Err(PErr {
+                pos : ind.pos
+            });
+        }
+
+        let val = T::from_str_radix(&digits, radix).map_err(|err| match err.kind()
+        {
+            IntErrorKind::NegOverflow => IntError::TooSmall,
+            _ => IntError::TooLarge,
+        });
+        Ok(PRes {
+            val,
+            pos : ind.pos.advance(&text[..end]),
+            remainder : &text[end..],
+        })
+    }
+}
+
+// A decimal float with an optional sign, fraction and exponent, such as `-12.5e3`, rounded to the nearest float
+#[inline]
+pub fn float<'a, T : FloatType>() -> impl Parser<'a, Float<T>> { float_with(FloatFormat::default()) }
+
+#[inline]
+pub fn float_with<'a, T : FloatType>(format : FloatFormat) -> impl Parser<'a, Float<T>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Float<T>> {
+        let text = ind.text;
+        let err = PErr {
+            pos : ind.pos
+        };
+        let mut literal = String::new();
+        let mut i = 0;
+        if text.starts_with('+') || text.starts_with('-')
+        {
+            literal.push_str(&text[..1]);
+            i = 1;
+        }
+
+        if format.special
+        {
+            for word in ["infinity", "inf", "nan"]
+            {
+                if text
+                    .get(i..i + word.len())
+                    .is_some_and(|w| w.eq_ignore_ascii_case(word))
+                {
+                    literal.push_str(word);
+                    let end = i + word.len();
+                    return Ok(PRes {
+                        val :       Float(literal.parse().map_err(|_| err.clone())?),
+                        pos :       ind.pos.advance(&text[..end]),
+                        remainder : &text[end..],
+                    });
+                }
+            }
+        }
+
+        let mut end = scan_digits(text, i, 10, format.separator, &mut literal);
+        if end == i
+        {
+            return Err(err);
+        }
+        if text[end..].starts_with('.') && text[end + 1..].starts_with(|c : char| c.is_ascii_digit())
+        {
+            literal.push('.');
+            end = scan_digits(text, end + 1, 10, format.separator, &mut literal);
+        }
+        if text[end..].starts_with(['e', 'E'])
+        {
+            // The exponent is only read if it has digits, otherwise the `e` is left for whatever follows
+            let mut exponent = String::from("e");
+            let mut j = end + 1;
+            if text[j..].starts_with(['+', '-'])
+            {
+                exponent.push_str(&text[j..j + 1]);
+                j += 1;
+            }
+            let exp_end = scan_digits(text, j, 10, format.separator, &mut exponent);
+            if exp_end > j
+            {
+                literal.push_str(&exponent);
+                end = exp_end;
+            }
+        }
+
+        Ok(PRes {
+            val :       Float(literal.parse().map_err(|_| err)?),
+            pos :       ind.pos.advance(&text[..end]),
+            remainder : &text[end..],
+        })
+    }
+}
+
+// Reads digits of the radix from `start`, pushing them onto `out` and returning where they end. Separators are only
+// read between two digits.
+fn scan_digits(text : &str, start : usize, radix : u32, separator : Option<char>, out : &mut String) -> usize
+{
+    let mut i = start;
+    let mut chars = text[start..].chars().peekable();
+    while let Some(c) = chars.next()
+    {
+        if c.is_digit(radix)
+        {
+            out.push(c);
+        }
+        else if Some(c) != separator || i == start || !chars.peek().is_some_and(|n| n.is_digit(radix))
+        {
+            break;
+        }
+        i += c.len_utf8();
+    }
+    i
+}
@@ -1,8 +1,8 @@
 use super::{
-    combiner::{smcomb, Combiner},
+    combiner::Combiner,
     combiners::{take_right, tuple_left_char_vec_to_str},
+    cst::{attempt, lookahead},
     defs::{Or2, Or3},
-    file_pos::FilePos,
     parser::{PErr, POut, PRes, PResData, Parser, ParserInput},
     parsers_core_ors::or2,
 };
@@ -147,7 +147,7 @@ pub fn not<'a, DatT : PResData>(
 ) -> impl Parser<'a, DatT>
 {
     move |ind : &ParserInput<'a>| -> POut<'a, DatT> {
-        match p(ind)
+        match lookahead(|| p(ind))
         {
             Ok(_v) => Err(PErr {
                 pos : ind.pos
@@ -175,7 +175,7 @@ pub fn and<'a, DatA : PResData, DatB : PResData, DatOut : PResData>(
 pub fn or<'a, DatT : PResData>(a : impl Parser<'a, DatT>, b : impl Parser<'a, DatT>) -> impl Parser<'a, DatT>
 {
     move |ind : &ParserInput<'a>| -> POut<'a, DatT> {
-        match a(ind)
+        match attempt(|| a(ind))
         {
             Ok(a) => Ok(PRes {
                 val :       a.val,
@@ -303,21 +303,28 @@ pub fn one_or_none<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parse
     })
 }
 
+// As many `p` as match, one after another, stopping early should one match without consuming anything as it would
+// otherwise match forever. Iterates rather than recursing, so that long repetitions cannot overflow the stack.
 #[inline]
 pub fn none_or_many<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parser<'a, Vec<DatT>>
 {
     move |ind : &ParserInput<'a>| -> POut<'a, Vec<DatT>> {
-        mod_val(
-            one_or_none(then(
-                p.clone(),
-                none_or_many(p.clone()),
-                smcomb(|a : DatT, mut b : Vec<DatT>| {
-                    b.insert(0, a);
-                    b
-                }),
-            )),
-            |opt_list| opt_list.unwrap_or_else(|| vec![]),
-        )(ind)
+        let mut items = vec![];
+        let mut cur = *ind;
+        while let Ok(res) = attempt(|| p(&cur))
+        {
+            if res.remainder.len() == cur.text.len()
+            {
+                break;
+            }
+            cur = res.to_in();
+            items.push(res.val);
+        }
+        Ok(PRes {
+            val :       items,
+            pos :       cur.pos,
+            remainder : cur.text,
+        })
     }
 }
 
@@ -327,6 +334,7 @@ pub fn one_or_many<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parse
     succeed_if(none_or_many(p), move |v : &PRes<'a, Vec<DatT>>| v.val.len() > 0)
 }
 
+// Any number of `pa` up to the first place that `pb` matches, which is tried before each `pa`
 #[inline]
 pub fn none_or_many_until<'a, DatA : PResData, DatB : PResData, DatOut : PResData>(
     pa : impl Parser<'a, DatA>,
@@ -334,7 +342,7 @@ pub fn none_or_many_until<'a, DatA : PResData, DatB : PResData, DatOut : PResDat
     comb : impl Combiner<'a, Vec<DatA>, DatB, DatOut>,
 ) -> impl Parser<'a, DatOut> // We either return the stop type or the combined type
 {
-    then(none_or_many(pa), pb, comb)
+    many_until(pa, pb, comb, 0)
 }
 
 #[inline]
@@ -344,7 +352,45 @@ pub fn one_or_many_until<'a, DatA : PResData, DatB : PResData, DatOut : PResData
     comb : impl Combiner<'a, Vec<DatA>, DatB, DatOut>,
 ) -> impl Parser<'a, DatOut>
 {
-    then(one_or_many(pa), pb, comb)
+    many_until(pa, pb, comb, 1)
+}
+
+fn many_until<'a, DatA : PResData, DatB : PResData, DatOut : PResData>(
+    pa : impl Parser<'a, DatA>,
+    pb : impl Parser<'a, DatB>,
+    comb : impl Combiner<'a, Vec<DatA>, DatB, DatOut>,
+    min : usize,
+) -> impl Parser<'a, DatOut>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, DatOut> {
+        let mut items = vec![];
+        let mut cur = *ind;
+        loop
+        {
+            if items.len() >= min
+            {
+                if let Ok(end) = attempt(|| pb(&cur))
+                {
+                    let many = PRes {
+                        val :       items,
+                        pos :       cur.pos,
+                        remainder : cur.text,
+                    };
+                    return comb(Ok(many), Ok(end));
+                }
+            }
+            let res = pa(&cur)?;
+            // A `pa` that consumes nothing would never reach `pb`
+            if res.remainder.len() == cur.text.len()
+            {
+                return Err(PErr {
+                    pos : cur.pos
+                });
+            }
+            cur = res.to_in();
+            items.push(res.val);
+        }
+    }
 }
 
 #[inline]
@@ -359,11 +405,15 @@ pub fn read_char_f<'a>(predicate : impl Fn(char) -> bool + Clone) -> impl Parser
                 {
                     Ok(PRes {
                         val :       c,
-                        pos :       FilePos {
-                            line :   ind.pos.line,
-                            column : ind.pos.column + 1,
+                        pos :       if c == '\n'
+                        {
+                            ind.pos.incr_line()
+                        }
+                        else
+                        {
+                            ind.pos.incr_col()
                         },
-                        remainder : ind.text.get(1..).unwrap(),
+                        remainder : &ind.text[c.len_utf8()..],
                     })
                 }
                 else
@@ -396,11 +446,9 @@ pub fn keyword<'a>(word : &'a str) -> impl Parser<'a, String>
     move |ind : &ParserInput<'a>| -> POut<'a, String> {
         if ind.text.starts_with(word)
         {
-            // TODO: This should not be handled here, as the keyword could contain either
-            // CR/LF
             Ok(PRes {
                 val :       String::from(word),
-                pos :       FilePos::new(ind.pos.line, ind.pos.column + word.len()),
+                pos :       ind.pos.advance(word),
                 remainder : ind.text.get(word.len()..).unwrap(),
             })
         }
@@ -413,9 +461,29 @@ pub fn keyword<'a>(word : &'a str) -> impl Parser<'a, String>
     }
 }
 
+// Like `keyword`, ignoring ASCII case, returning the text as it appears in the input
+#[inline]
+pub fn keyword_ci<'a>(word : &'a str) -> impl Parser<'a, String>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        match ind.text.get(..word.len())
+        {
+            Some(matched) if matched.eq_ignore_ascii_case(word) => Ok(PRes {
+                val :       String::from(matched),
+                pos :       ind.pos.advance(matched),
+                remainder : ind.text.get(word.len()..).unwrap(),
+            }),
+            _ => Err(PErr {
+                pos : ind.pos
+            }),
+        }
+    }
+}
+
 #[inline]
 pub fn any_char<'a>() -> impl Parser<'a, char> { read_char_f(|_| true) }
 
+// Every character up to the first place that `p` matches, along with what `p` matched
 #[inline]
 pub fn consume_chars_until<'a, DatEnd : PResData>(p : impl Parser<'a, DatEnd>) -> impl Parser<'a, (String, DatEnd)>
 {
@@ -440,29 +508,21 @@ pub fn thenr<'a, DatA : PResData, DatB : PResData, DatOut : PResData>(
             {
                 (Some(sa), Some(_sb)) =>
                 {
-                    match all(a.clone())(&ParserInput {
-                        text : sa,
-                        pos :  ind.pos,
-                    })
+                    // Both are attempted together, so that nothing `a` records is kept should `b` then fail
+                    let pair = attempt(|| {
+                        let a_succ = all(a.clone())(&ParserInput {
+                            text : sa,
+                            pos :  ind.pos,
+                        })?;
+                        let b_succ = b(&ParserInput {
+                            text : ind.text,
+                            pos :  a_succ.pos,
+                        })?;
+                        Ok((a_succ, b_succ))
+                    });
+                    if let Ok((a_succ, b_succ)) = pair
                     {
-                        Ok(a_succ) =>
-                        {
-                            let b_res = b(&ParserInput {
-                                text : ind.text,
-                                pos :  a_succ.pos,
-                            });
-                            match b_res
-                            {
-                                Ok(b_succ) =>
-                                {
-                                    return comb(Ok(a_succ), Ok(b_succ));
-                                },
-                                Err(_b_err) =>
-                                {},
-                            }
-                        },
-                        Err(_a_err) =>
-                        {},
+                        return comb(Ok(a_succ), Ok(b_succ));
                     }
                 },
                 (_, _) =>
@@ -484,7 +544,7 @@ pub fn no_consume<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parser
     move |ind : &ParserInput<'a>| -> POut<'a, DatT> {
         let always_res = always(|| ())(ind)?;
 
-        p(ind).map(|p_res| -> PRes<'a, DatT> {
+        lookahead(|| p(ind)).map(|p_res| -> PRes<'a, DatT> {
             PRes {
                 val :       p_res.val,
                 pos :       always_res.pos,
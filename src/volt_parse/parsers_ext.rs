@@ -2,23 +2,19 @@ use super::combiner::*;
 use super::combiners::*;
 use super::parser::*;
 use super::parsers_core::*;
+use super::strings::*;
 use std::fmt::Debug;
 
+// The raw two character escape sequence, such as `\n`, see `string_literal` to decode escapes
 pub fn escaped_char<'a>() -> impl Parser<'a, String>
 {
     then(char_single('\\'), any_char(), smcomb(|a, b| format!("{}{}", a, b)))
 }
 
-pub fn normal_string<'a>() -> impl Parser<'a, char>
+// A double quoted string, with its escapes decoded
+pub fn normal_string<'a>() -> impl Parser<'a, String>
 {
-    mod_val(
-        then(
-            char_single('"'),
-            none_or_many_until(any_char(), char_single('"'), take_left),
-            take_left,
-        ),
-        |vs| vs, //.fold(String::from(""), |str,c|str.push(c))
-    )
+    mod_val(string_literal(StringFormat::double()), |s| s.into_owned())
 }
 
 pub fn lowercase<'a>() -> impl Parser<'a, char> { read_char_f(|c| c.is_lowercase()) }
@@ -35,14 +31,15 @@ pub fn ascii_digit<'a>() -> impl Parser<'a, char> { read_char_f(|c| c.is_ascii_d
 
 pub fn newline<'a>() -> impl Parser<'a, String> { or(keyword("\r\n"), keyword("\n")) }
 
-pub fn air<'a, DatT : PResData>() -> impl Parser<'a, String> { or(display(char_in_str(" \t")), newline()) }
+pub fn air<'a>() -> impl Parser<'a, String> { or(display(char_in_str(" \t")), newline()) }
+
+// Any amount of air, including none at all
+pub fn many_air<'a>() -> impl Parser<'a, String> { mod_val(none_or_many(air()), |v| v.concat()) }
 
 pub fn comma<'a>() -> impl Parser<'a, char> { char_single(',') }
 
 pub fn dot<'a>() -> impl Parser<'a, char> { char_single('.') }
 
-// pub fn integer_str<'a>() -> impl Parser<'a, String> { one_or_many(digit(), disp_comb) }
-
 // fn in_air<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parser<'a,
 // DatT> {
 //     then(air(), then(p, air(), l_comb), r_comb)
@@ -84,3 +81,28 @@ pub fn infixp<'a, DatLeft : PResData, DatParent : PResData, DatRight : PResData>
 }
 
 pub fn maybe<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parser<'a, Option<DatT>> { one_or_none(p) }
+
+// Zero or more `p`, each separated by a `sep`
+pub fn sep_by<'a, DatT : PResData, DatSep : PResData>(
+    p : impl Parser<'a, DatT>,
+    sep : impl Parser<'a, DatSep>,
+) -> impl Parser<'a, Vec<DatT>>
+{
+    mod_val(maybe(sep_by1(p, sep)), |v| v.unwrap_or_default())
+}
+
+// One or more `p`, each separated by a `sep`
+pub fn sep_by1<'a, DatT : PResData, DatSep : PResData>(
+    p : impl Parser<'a, DatT>,
+    sep : impl Parser<'a, DatSep>,
+) -> impl Parser<'a, Vec<DatT>>
+{
+    then(
+        p.clone(),
+        none_or_many(then(sep, p, take_right)),
+        smcomb(|first, mut rest : Vec<DatT>| {
+            rest.insert(0, first);
+            rest
+        }),
+    )
+}
@@ -0,0 +1,9 @@
+// Parsing of file system paths in the POSIX and Windows dialects, and their normalization into absolute paths that
+// cannot go above their root
+pub mod grammar;
+pub mod parser;
+pub mod path;
+
+pub use grammar::*;
+pub use parser::*;
+pub use path::*;
@@ -0,0 +1,47 @@
+use crate::grammar::{build::*, Grammar};
+
+// The same paths as `path_parser` in the POSIX dialect, written as an inspectable grammar
+pub fn path_grammar() -> Grammar
+{
+    Grammar::new(vec![
+        define(
+            "path",
+            then(
+                choice(vec![
+                    then(rule("home"), maybe(rule("root"))),
+                    rule("root"),
+                    rule("local_path"),
+                ]),
+                not(any_char()),
+            ),
+        ),
+        define("root", then(rule("delim"), maybe(rule("local_path")))),
+        define(
+            "local_path",
+            then(
+                rule("path_piece"),
+                maybe(then(rule("delim"), maybe(rule("local_path")))),
+            ),
+        ),
+        define("path_piece", choice(vec![rule("up"), rule("name"), rule("current")])),
+        // A name may contain dots, but may not be a single dot
+        define(
+            "name",
+            then(
+                not(then(keyword("."), not(rule("name_char")))),
+                one_or_many(or(rule("name_char"), rule("single_dot"))),
+            ),
+        ),
+        // Anything but a dot, a delimiter or a control character
+        define(
+            "name_char",
+            then(not(char_range('\0', '\x1f')), char_not_in_str("./\x7f")),
+        ),
+        define("single_dot", then(keyword("."), not(keyword(".")))),
+        define("current", keyword(".")),
+        define("up", keyword("..")),
+        define("delim", one_or_many(keyword("/"))),
+        define("home", keyword("~")),
+    ])
+    .unwrap()
+}
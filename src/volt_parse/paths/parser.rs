@@ -0,0 +1,267 @@
+use super::path::{PathDialect, PathPiece, PathPrefix, UnsafePath, UnsafePathType};
+use crate::prelude::*;
+
+// Parses a single dot, failing if there is another dot after this one - does not consume any following characters
+pub fn single_dot<'a>() -> impl Parser<'a, String>
+{
+    then(
+        keyword("."),
+        not(no_consume(keyword(".")), || String::from("")),
+        take_left,
+    )
+}
+
+pub fn name_parser<'a>() -> impl Parser<'a, PathPiece> { name_parser_with(PathDialect::Posix) }
+
+pub fn name_parser_with<'a>(dialect : PathDialect) -> impl Parser<'a, PathPiece>
+{
+    mod_val(
+        fail_if(
+            one_or_many(or(display(read_char_f(move |c| dialect.is_name_char(c))), single_dot())),
+            |res| res.val.len() == 1 && res.val[0] == ".",
+        ),
+        |chars| PathPiece::Name(chars.concat()),
+    )
+}
+
+pub fn current_parser<'a>() -> impl Parser<'a, PathPiece> { mod_val(keyword("."), |_| PathPiece::Current) }
+
+pub fn up_parser<'a>() -> impl Parser<'a, PathPiece> { mod_val(keyword(".."), |_| PathPiece::Up) }
+
+pub fn delim_parser<'a>() -> impl Parser<'a, PathPiece> { delim_parser_with(PathDialect::Posix) }
+
+pub fn delim_parser_with<'a>(dialect : PathDialect) -> impl Parser<'a, PathPiece>
+{
+    mod_val(one_or_many(read_char_f(move |c| dialect.is_separator(c))), |_| {
+        PathPiece::Delim
+    })
+}
+
+pub fn home_parser<'a>() -> impl Parser<'a, PathPiece> { mod_val(keyword("~"), |_| PathPiece::Home) }
+
+pub fn chain_path_piece_parser<'a>() -> impl Parser<'a, PathPiece> { chain_path_piece_parser_with(PathDialect::Posix) }
+
+pub fn chain_path_piece_parser_with<'a>(dialect : PathDialect) -> impl Parser<'a, PathPiece>
+{
+    or(or(up_parser(), name_parser_with(dialect)), current_parser())
+}
+
+pub fn compress<'a, DatT : PResData>(a : POut<'a, DatT>, b : POut<'a, Option<Vec<DatT>>>) -> POut<'a, Vec<DatT>>
+{
+    gen_comb(a, b, |l, r : Option<Vec<DatT>>| match r
+    {
+        Some(mut rs) =>
+        {
+            rs.insert(0, l);
+            rs
+        },
+        None => vec![l],
+    })
+}
+
+pub fn local_path_parser<'a>() -> impl Parser<'a, Vec<PathPiece>> { local_path_parser_with(PathDialect::Posix) }
+
+pub fn local_path_parser_with<'a>(dialect : PathDialect) -> impl Parser<'a, Vec<PathPiece>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Vec<PathPiece>> {
+        then(
+            chain_path_piece_parser_with(dialect),
+            maybe(then(
+                delim_parser_with(dialect),
+                maybe(local_path_parser_with(dialect)),
+                compress,
+            )),
+            compress,
+        )(ind)
+    }
+}
+
+// Removes delimeters, home tilda, and current dot (as on this layer it isn't helpful)
+fn filter_meta_path(piece : &&PathPiece) -> bool
+{
+    !matches!(piece, PathPiece::Delim | PathPiece::Home | PathPiece::Current)
+}
+
+fn meta_filtered(pieces : Vec<PathPiece>) -> Vec<PathPiece>
+{
+    pieces.iter().filter(filter_meta_path).cloned().collect()
+}
+
+// Supports absolute paths too
+pub fn path_parser<'a>() -> impl Parser<'a, UnsafePath> { path_parser_with(PathDialect::Posix) }
+
+pub fn path_parser_with<'a>(dialect : PathDialect) -> impl Parser<'a, UnsafePath>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, UnsafePath> {
+        match dialect
+        {
+            PathDialect::Posix => posix_path_parser()(ind),
+            PathDialect::Windows => windows_path_parser()(ind),
+        }
+    }
+}
+
+fn posix_path_parser<'a>() -> impl Parser<'a, UnsafePath>
+{
+    // Possibilities:
+    // * Local Path
+    // * Home
+    // * Home -> Local Path
+    // * Root
+    // * Root -> Local Path
+
+    let root_parser = or(
+        then(delim_parser(), local_path_parser(), take_right),
+        mod_val(delim_parser(), |_| vec![]),
+    );
+
+    mod_val(
+        all(or(
+            or(
+                // Paths that start with a '~' (relative to HOME - which are technically still absolute paths)
+                mod_val(
+                    or(
+                        then(home_parser(), root_parser.clone(), take_right),
+                        mod_val(home_parser(), |_| vec![]),
+                    ),
+                    |pieces| (UnsafePathType::Home, pieces),
+                ),
+                // Paths that start with a delimiter (absolute paths)
+                mod_val(root_parser, |pieces| (UnsafePathType::Absolute, pieces)),
+            ),
+            // Relative paths
+            mod_val(local_path_parser(), |pieces| (UnsafePathType::Relative, pieces)),
+        )),
+        |(path_type, pieces)| UnsafePath {
+            pieces : meta_filtered(pieces),
+            path_type,
+            prefix : None,
+            dialect : PathDialect::Posix,
+            verbatim : false,
+        },
+    )
+}
+
+// A drive letter and its colon, such as `C:`
+pub fn drive_parser<'a>() -> impl Parser<'a, PathPrefix>
+{
+    mod_val(
+        then(read_char_f(|c| c.is_ascii_alphabetic()), char_single(':'), take_left),
+        |letter| PathPrefix::Drive(letter.to_ascii_uppercase()),
+    )
+}
+
+// A network share, such as `\\server\share`
+pub fn unc_parser<'a>() -> impl Parser<'a, PathPrefix>
+{
+    let dialect = PathDialect::Windows;
+    let sep = || read_char_f(move |c| dialect.is_separator(c));
+    let name = || display(name_parser_with(dialect));
+    mod_val(
+        then(
+            then(sep(), sep(), take_right),
+            then(name(), then(sep(), name(), take_right), left_right),
+            take_right,
+        ),
+        |(server, share)| PathPrefix::Unc {
+            server,
+            share,
+        },
+    )
+}
+
+// Possibilities:
+// * Verbatim, `\\?\` followed by a drive, `UNC\server\share` or neither, and then names separated by `\` only
+// * Network share -> Root -> Local Path
+// * Drive -> Root -> Local Path, where the root is optional
+// * Root -> Local Path
+// * Local Path
+fn windows_path_parser<'a>() -> impl Parser<'a, UnsafePath>
+{
+    let dialect = PathDialect::Windows;
+    move |ind : &ParserInput<'a>| -> POut<'a, UnsafePath> {
+        if ind.text.starts_with(r"\\?\")
+        {
+            return verbatim_path_parser()(ind);
+        }
+
+        let prefix = if ind.text.chars().take(2).filter(|c| dialect.is_separator(*c)).count() == 2
+        {
+            Some(unc_parser()(ind)?)
+        }
+        else
+        {
+            drive_parser()(ind).ok()
+        };
+        let rest = prefix.as_ref().map_or(*ind, |p| p.to_in());
+        let root = maybe(delim_parser_with(dialect))(&rest)?;
+        let is_unc = matches!(prefix.as_ref().map(|p| &p.val), Some(PathPrefix::Unc { .. }));
+        let path_type = if root.val.is_some() || is_unc
+        {
+            UnsafePathType::Absolute
+        }
+        else
+        {
+            UnsafePathType::Relative
+        };
+
+        let local = local_path_parser_with(dialect);
+        let pieces = if prefix.is_none() && root.val.is_none()
+        {
+            all(local)(&rest)?
+        }
+        else
+        {
+            all(mod_val(maybe(local), |pieces| pieces.unwrap_or_default()))(&root.to_in())?
+        };
+        Ok(pieces.with_val(UnsafePath {
+            pieces : meta_filtered(pieces.val.clone()),
+            path_type,
+            prefix : prefix.map(|p| p.val),
+            dialect,
+            verbatim : false,
+        }))
+    }
+}
+
+// Everything after `\\?\` is passed to the file system as written, so only `\` separates pieces and every piece is a
+// name
+fn verbatim_path_parser<'a>() -> impl Parser<'a, UnsafePath>
+{
+    let sep = || char_single('\\');
+    let name = || {
+        mod_val(
+            one_or_many(read_char_f(|c| c != '\\' && c >= ' ' && c != '\x7f')),
+            |cs| PathPiece::Name(cs.into_iter().collect()),
+        )
+    };
+    let unc = mod_val(
+        then(
+            keyword(r"UNC\"),
+            then(display(name()), then(sep(), display(name()), take_right), left_right),
+            take_right,
+        ),
+        |(server, share)| PathPrefix::Unc {
+            server,
+            share,
+        },
+    );
+    move |ind : &ParserInput<'a>| -> POut<'a, UnsafePath> {
+        let body = keyword(r"\\?\")(ind)?.to_in();
+        let prefix = or(unc.clone(), drive_parser())(&body).ok();
+        let rest = prefix.as_ref().map_or(body, |p| p.to_in());
+        let pieces = match prefix
+        {
+            Some(_) => none_or_many(then(sep(), name(), take_right))(&rest)?,
+            None => sep_by1(name(), sep())(&rest)?,
+        };
+        let end = all(maybe(sep()))(&pieces.to_in())?;
+        Ok(end.with_val(UnsafePath {
+            pieces :    pieces.val,
+            path_type : UnsafePathType::Absolute,
+            prefix :    prefix.map(|p| p.val),
+            dialect :   PathDialect::Windows,
+            verbatim :  true,
+        }))
+    }
+}
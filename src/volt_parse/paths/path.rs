@@ -0,0 +1,255 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathPiece
+{
+    Name(String),
+    Current,
+    Up,
+    Delim,
+    Home,
+}
+
+// How paths are written, which decides their separators, the characters allowed in names and the prefixes they may
+// begin with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathDialect
+{
+    // Separated by `/`, and may begin at the home directory with `~`
+    Posix,
+    // Separated by `\` or `/`, and may begin with a drive letter, a network share or `\\?\`
+    Windows,
+}
+
+// Where a Windows path begins, as each drive and network share has its own root
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathPrefix
+{
+    // Such as `C:`, always held in upper case
+    Drive(char),
+    // Such as `\\server\share`
+    Unc
+    {
+        server : String,
+        share :  String,
+    },
+}
+
+// A path that is fully validated, and is always absolute
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SafePath
+{
+    // The drive or network share that the path is on, or `None` for the single root of a POSIX path
+    pub prefix : Option<PathPrefix>,
+    // The path must be completely absolute, and thus cannot have anything except names
+    pub pieces : Vec<String>,
+}
+
+// This is to denote a path that has been 'parsed' but is not yet safe to perform on-disk file access operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsafePath
+{
+    pub pieces :    Vec<PathPiece>,
+    pub path_type : UnsafePathType,
+    // The drive or network share the path begins with, where a drive without a delimiter after it, such as `C:a`, is
+    // relative to the current directory of that drive
+    pub prefix :    Option<PathPrefix>,
+    pub dialect :   PathDialect,
+    // Whether the path began with `\\?\`, after which every piece is taken as a name, even `.` and `..`
+    pub verbatim :  bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnsafePathType
+{
+    Relative,
+    Absolute, // This path begins at home
+    Home,
+}
+
+// Why a path could not be made safe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError
+{
+    // The `..` at this index of the pieces would go above the root
+    EscapesRoot(usize),
+}
+
+impl Display for PathError
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            PathError::EscapesRoot(index) => write!(f, "path piece {index} goes above the root"),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+impl PathDialect
+{
+    // The separator used when a path is displayed
+    pub fn separator(&self) -> char
+    {
+        match self
+        {
+            PathDialect::Posix => '/',
+            PathDialect::Windows => '\\',
+        }
+    }
+
+    pub fn is_separator(&self, c : char) -> bool { c == '/' || (*self == PathDialect::Windows && c == '\\') }
+
+    // Whether the character may appear within a name, other than a dot, which names may only contain one at a time
+    pub fn is_name_char(&self, c : char) -> bool
+    {
+        let reserved = match self
+        {
+            PathDialect::Posix => "",
+            PathDialect::Windows => "<>:\"|?*",
+        };
+        c >= ' ' && c != '\x7f' && c != '.' && !self.is_separator(c) && !reserved.contains(c)
+    }
+}
+
+impl From<SafePath> for UnsafePath
+{
+    fn from(sp : SafePath) -> Self
+    {
+        UnsafePath {
+            pieces :    sp.pieces.iter().map(|name| PathPiece::Name(name.to_owned())).collect(),
+            path_type : UnsafePathType::Absolute,
+            dialect :   sp.dialect(),
+            prefix :    sp.prefix,
+            verbatim :  false,
+        }
+    }
+}
+
+impl UnsafePath
+{
+    // Resolves every `.` and `..` to give the absolute path this refers to, where relative paths begin at `base` and
+    // home paths at `home`. A path without a prefix stays on the drive or share of `base`, and a drive relative path
+    // such as `C:a` begins at `base` if it is on the same drive, or else at the root of the drive.
+    pub fn normalize(&self, base : &SafePath, home : &SafePath) -> Result<SafePath, PathError>
+    {
+        let mut path = match (&self.path_type, &self.prefix)
+        {
+            (UnsafePathType::Home, _) => home.clone(),
+            (UnsafePathType::Relative, Some(prefix)) if base.prefix.as_ref() != Some(prefix) => SafePath {
+                prefix : Some(prefix.clone()),
+                pieces : vec![],
+            },
+            (UnsafePathType::Relative, _) => base.clone(),
+            (UnsafePathType::Absolute, prefix) => SafePath {
+                prefix : prefix.clone().or_else(|| base.prefix.clone()),
+                pieces : vec![],
+            },
+        };
+        for (i, piece) in self.pieces.iter().enumerate()
+        {
+            match piece
+            {
+                PathPiece::Name(name) => path.pieces.push(name.clone()),
+                PathPiece::Up =>
+                {
+                    path.pieces.pop().ok_or(PathError::EscapesRoot(i))?;
+                },
+                PathPiece::Current | PathPiece::Delim | PathPiece::Home => (),
+            }
+        }
+        Ok(path)
+    }
+}
+
+impl Display for UnsafePath
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let sep = self.dialect.separator();
+        if self.verbatim
+        {
+            f.write_str(r"\\?\")?;
+        }
+        match &self.prefix
+        {
+            Some(PathPrefix::Drive(letter)) => write!(f, "{letter}:")?,
+            Some(PathPrefix::Unc {
+                server,
+                share,
+            }) if self.verbatim => write!(f, r"UNC\{server}\{share}")?,
+            Some(PathPrefix::Unc {
+                server,
+                share,
+            }) => write!(f, r"\\{server}\{share}")?,
+            None => (),
+        }
+        let start = match (&self.path_type, &self.prefix)
+        {
+            (UnsafePathType::Relative, Some(_)) => String::new(),
+            (UnsafePathType::Relative, None) => format!(".{sep}"),
+            // A verbatim path without a prefix begins straight after `\\?\`
+            (UnsafePathType::Absolute, None) if self.verbatim => String::new(),
+            (UnsafePathType::Absolute, _) => String::from(sep),
+            (UnsafePathType::Home, _) => format!("~{sep}"),
+        };
+        let pieces = self.pieces.iter().map(|p| p.to_str()).collect::<Vec<_>>();
+        let message = start + pieces.join(&String::from(sep)).as_str();
+        f.write_str(message.as_str())
+    }
+}
+
+impl SafePath
+{
+    pub fn root() -> SafePath
+    {
+        SafePath {
+            prefix : None,
+            pieces : vec![],
+        }
+    }
+
+    pub fn dialect(&self) -> PathDialect
+    {
+        if self.prefix.is_some()
+        {
+            PathDialect::Windows
+        }
+        else
+        {
+            PathDialect::Posix
+        }
+    }
+}
+
+impl Default for SafePath
+{
+    fn default() -> Self { Self::root() }
+}
+
+impl Display for SafePath
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result { UnsafePath::from(self.clone()).fmt(f) }
+}
+
+impl PathPiece
+{
+    pub fn to_str<'a>(&'a self) -> &'a str
+    {
+        match self
+        {
+            PathPiece::Name(name) => name,
+            PathPiece::Current => ".",
+            PathPiece::Up => "..",
+            PathPiece::Delim => "/",
+            PathPiece::Home => "~",
+        }
+    }
+}
+
+impl Display for PathPiece
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str(self.to_str()) }
+}
@@ -0,0 +1,170 @@
+use super::parser::{POut, PResData, Parser, ParserInput};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+// What was measured for a single named rule
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RuleStats
+{
+    pub calls :      usize,
+    pub successes :  usize,
+    // How many times the rule failed, forcing whatever called it to backtrack
+    pub backtracks : usize,
+    pub bytes :      usize,
+    // Time spent within the rule, including within any other profiled rules it called
+    pub time :       Duration,
+    // Time spent within the rule, excluding time spent within any other profiled rules it called
+    pub self_time :  Duration,
+}
+
+// Everything measured during a call to `with_profile`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Profile
+{
+    pub rules : HashMap<String, RuleStats>,
+}
+
+struct Profiler
+{
+    rules :    HashMap<String, RuleStats>,
+    // The time spent within profiled rules called by each rule currently being parsed
+    children : Vec<Duration>,
+}
+
+thread_local! {
+    static PROFILER : RefCell<Option<Profiler>> = const { RefCell::new(None) };
+}
+
+// Runs `f`, measuring every `profile` rule that it attempts to parse
+pub fn with_profile<R>(f : impl FnOnce() -> R) -> (R, Profile)
+{
+    let outer = PROFILER.with(|p| {
+        p.borrow_mut().replace(Profiler {
+            rules :    HashMap::new(),
+            children : vec![Duration::ZERO],
+        })
+    });
+    let res = f();
+    let profiler = PROFILER.with(|p| std::mem::replace(&mut *p.borrow_mut(), outer));
+    (
+        res,
+        Profile {
+            rules : profiler.map(|p| p.rules).unwrap_or_default(),
+        },
+    )
+}
+
+// Names `p` so that it is measured while within `with_profile`, otherwise this does nothing at all
+#[inline]
+pub fn profile<'a, DatT : PResData>(name : &'a str, p : impl Parser<'a, DatT>) -> impl Parser<'a, DatT>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, DatT> {
+        let profiling = PROFILER.with(|prof| match prof.borrow_mut().as_mut()
+        {
+            Some(prof) =>
+            {
+                prof.children.push(Duration::ZERO);
+                true
+            },
+            None => false,
+        });
+        if !profiling
+        {
+            return p(ind);
+        }
+
+        let start = Instant::now();
+        let res = p(ind);
+        let elapsed = start.elapsed();
+
+        PROFILER.with(|prof| {
+            if let Some(prof) = prof.borrow_mut().as_mut()
+            {
+                let children = prof.children.pop().unwrap_or_default();
+                if let Some(parent) = prof.children.last_mut()
+                {
+                    *parent += elapsed;
+                }
+
+                let stats = prof.rules.entry(name.to_string()).or_default();
+                stats.calls += 1;
+                stats.time += elapsed;
+                stats.self_time += elapsed.saturating_sub(children);
+                match &res
+                {
+                    Ok(succ) =>
+                    {
+                        stats.successes += 1;
+                        stats.bytes += ind.text.len() - succ.remainder.len();
+                    },
+                    Err(_) => stats.backtracks += 1,
+                }
+            }
+        });
+        res
+    }
+}
+
+impl RuleStats
+{
+    pub fn success_rate(&self) -> f64
+    {
+        if self.calls == 0
+        {
+            0.0
+        }
+        else
+        {
+            self.successes as f64 / self.calls as f64
+        }
+    }
+}
+
+impl Profile
+{
+    // Every rule, the most time consuming first
+    pub fn sorted(&self) -> Vec<(&str, &RuleStats)>
+    {
+        let mut rules = self
+            .rules
+            .iter()
+            .map(|(name, stats)| (name.as_str(), stats))
+            .collect::<Vec<_>>();
+        rules.sort_by(|(an, a), (bn, b)| b.time.cmp(&a.time).then_with(|| an.cmp(bn)));
+        rules
+    }
+}
+
+// A table of every rule, the most time consuming first
+impl Display for Profile
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let rules = self.sorted();
+        let width = rules.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(4);
+        writeln!(
+            f,
+            "{:<width$} {:>10} {:>10} {:>8} {:>10} {:>12} {:>12}",
+            "rule", "calls", "backtracks", "success", "bytes", "time", "self time"
+        )?;
+        for (name, stats) in rules
+        {
+            writeln!(
+                f,
+                "{:<width$} {:>10} {:>10} {:>7.1}% {:>10} {:>12} {:>12}",
+                name,
+                stats.calls,
+                stats.backtracks,
+                stats.success_rate() * 100.0,
+                stats.bytes,
+                format!("{:.3?}", stats.time),
+                format!("{:.3?}", stats.self_time),
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use super::parser::{PErr, POut, PRes, Parser, ParserInput};
+
+// The text matched by a regular expression along with its capture groups
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegexMatch
+{
+    pub text :   String,
+    // Every capture group, with the whole match first, or `None` where a group did not take part in the match
+    pub groups : Vec<Option<String>>,
+    // The name of each capture group, if it has one
+    pub names :  Vec<Option<String>>,
+}
+
+impl RegexMatch
+{
+    pub fn group(&self, index : usize) -> Option<&str> { self.groups.get(index)?.as_deref() }
+
+    pub fn name(&self, name : &str) -> Option<&str>
+    {
+        let index = self.names.iter().position(|n| n.as_deref() == Some(name))?;
+        self.group(index)
+    }
+}
+
+// Matches the regular expression at the current position, never skipping ahead to find a match
+//
+// Panics if the pattern is invalid, see `try_regex` to handle this instead
+#[inline]
+pub fn regex<'a>(pattern : &str) -> impl Parser<'a, RegexMatch>
+{
+    match try_regex(pattern)
+    {
+        Ok(p) => p,
+        Err(err) => panic!("invalid regex pattern {pattern:?}: {err}"),
+    }
+}
+
+#[inline]
+pub fn try_regex<'a>(pattern : &str) -> Result<impl Parser<'a, RegexMatch>, ::regex::Error>
+{
+    let re = anchored(pattern)?;
+    let names = re.capture_names().map(|n| n.map(String::from)).collect::<Vec<_>>();
+    Ok(move |ind : &ParserInput<'a>| -> POut<'a, RegexMatch> {
+        match re.captures(ind.text)
+        {
+            Some(caps) =>
+            {
+                let matched = &ind.text[..caps.get(0).unwrap().end()];
+                Ok(PRes {
+                    val :       RegexMatch {
+                        text :   String::from(matched),
+                        groups : caps.iter().map(|g| g.map(|g| String::from(g.as_str()))).collect(),
+                        names :  names.clone(),
+                    },
+                    pos :       ind.pos.advance(matched),
+                    remainder : &ind.text[matched.len()..],
+                })
+            },
+            None => Err(PErr {
+                pos : ind.pos
+            }),
+        }
+    })
+}
+
+// Like `regex`, returning only the text matched, which avoids the cost of finding the capture groups
+//
+// Panics if the pattern is invalid
+#[inline]
+pub fn regex_str<'a>(pattern : &str) -> impl Parser<'a, String>
+{
+    let re = anchored(pattern).unwrap_or_else(|err| panic!("invalid regex pattern {pattern:?}: {err}"));
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        match re.find(ind.text)
+        {
+            Some(m) =>
+            {
+                let matched = m.as_str();
+                Ok(PRes {
+                    val :       String::from(matched),
+                    pos :       ind.pos.advance(matched),
+                    remainder : &ind.text[m.end()..],
+                })
+            },
+            None => Err(PErr {
+                pos : ind.pos
+            }),
+        }
+    }
+}
+
+fn anchored(pattern : &str) -> Result<::regex::Regex, ::regex::Error>
+{
+    ::regex::Regex::new(&format!(r"\A(?:{pattern})"))
+}
@@ -0,0 +1,50 @@
+use super::{
+    file_pos::FilePos,
+    parser::{POut, PRes, PResData, Parser, ParserInput},
+};
+
+// The region of the input that some value was parsed from, the end is exclusive
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span
+{
+    pub start : FilePos,
+    pub end :   FilePos,
+}
+
+impl Span
+{
+    pub fn new(start : FilePos, end : FilePos) -> Self
+    {
+        Self {
+            start,
+            end,
+        }
+    }
+}
+
+// A parsed value along with where it was parsed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned<DatT>
+{
+    pub val :  DatT,
+    pub span : Span,
+}
+
+// Records the span of whatever `p` parses
+#[inline]
+pub fn spanned<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parser<'a, Spanned<DatT>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Spanned<DatT>> {
+        p(ind).map(|res| PRes {
+            val :       Spanned {
+                val :  res.val,
+                span : Span::new(ind.pos, res.pos),
+            },
+            pos :       res.pos,
+            remainder : res.remainder,
+        })
+    }
+}
+
+// The text that was consumed to go from `before` to `after`, where `after` is some remainder of `before`
+pub fn consumed<'a>(before : &'a str, after : &'a str) -> &'a str { &before[..before.len() - after.len()] }
@@ -0,0 +1,255 @@
+use super::parser::{PErr, POut, PRes, Parser, ParserInput};
+use std::borrow::Cow;
+
+// Which backslash escapes a string literal decodes, when it decodes any
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escapes
+{
+    // `\n`, `\r`, `\t`, `\0`, `\\`, `\"`, `\'`, `\xNN`, `\u{N..}` and `\uNNNN`
+    Standard,
+    // Those of RFC 8259, being `\"`, `\\`, `\/`, `\b`, `\f`, `\n`, `\r`, `\t` and `\uNNNN`, where control characters
+    // must be escaped rather than written directly
+    Json,
+    // Those of TOML, being `\"`, `\\`, `\b`, `\f`, `\n`, `\r`, `\t`, `\uNNNN` and `\UNNNNNNNN`, where control
+    // characters other than tabs and newlines must be escaped. Within a multiline string a backslash at the end
+    // of a line skips the newline and any whitespace after it.
+    Toml,
+}
+
+// How a quoted string literal is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringFormat
+{
+    // Written before the opening quote, such as the `r` of `r"raw"`
+    pub prefix :       &'static str,
+    // Opens and closes the string
+    pub quote :        &'static str,
+    // Whether backslash escapes are decoded, otherwise the text is taken exactly as written
+    pub escapes :      bool,
+    // Which escapes are decoded, and which characters must be escaped
+    pub escape_style : Escapes,
+    // Whether the string may span multiple lines
+    pub multiline :    bool,
+}
+
+impl StringFormat
+{
+    // "text", with escapes
+    pub fn double() -> Self
+    {
+        Self {
+            prefix :       "",
+            quote :        "\"",
+            escapes :      true,
+            escape_style : Escapes::Standard,
+            multiline :    false,
+        }
+    }
+
+    // 'text', with escapes
+    pub fn single() -> Self
+    {
+        Self {
+            quote : "'",
+            ..Self::double()
+        }
+    }
+
+    // r"text", without escapes
+    pub fn raw() -> Self
+    {
+        Self {
+            prefix :       "r",
+            quote :        "\"",
+            escapes :      false,
+            escape_style : Escapes::Standard,
+            multiline :    true,
+        }
+    }
+
+    // "text", with the escapes of JSON
+    pub fn json() -> Self
+    {
+        Self {
+            escape_style : Escapes::Json,
+            ..Self::double()
+        }
+    }
+
+    // """text""", with escapes, which may span multiple lines
+    pub fn triple() -> Self
+    {
+        Self {
+            quote : "\"\"\"",
+            multiline : true,
+            ..Self::double()
+        }
+    }
+}
+
+// A quoted string, decoding the escapes of its format, where a pair of `\uNNNN` escapes may encode a surrogate pair.
+// The text between the quotes is borrowed from the input unless an escape had to be decoded.
+//
+// Fails at the backslash of an invalid escape, at a control character that JSON or TOML requires to be escaped, or at
+// the opening quote if the string is never closed.
+#[inline]
+pub fn string_literal<'a>(format : StringFormat) -> impl Parser<'a, Cow<'a, str>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Cow<'a, str>> {
+        let text = ind.text;
+        let err_at = |i : usize| PErr {
+            pos : ind.pos.advance(&text[..i]),
+        };
+        let open = format.prefix.len() + format.quote.len();
+        if !text.starts_with(format.prefix) || !text[format.prefix.len()..].starts_with(format.quote)
+        {
+            return Err(err_at(0));
+        }
+
+        let style = format.escapes.then_some(format.escape_style);
+        let mut decoded : Option<String> = None;
+        let mut i = open;
+        loop
+        {
+            let rest = &text[i..];
+            if rest.starts_with(format.quote)
+            {
+                let end = i + format.quote.len();
+                let val = match decoded
+                {
+                    Some(s) => Cow::Owned(s),
+                    None => Cow::Borrowed(&text[open..i]),
+                };
+                return Ok(PRes {
+                    val,
+                    pos : ind.pos.advance(&text[..end]),
+                    remainder : &text[end..],
+                });
+            }
+
+            match rest.chars().next()
+            {
+                None => return Err(err_at(0)),
+                Some('\n') if !format.multiline => return Err(err_at(0)),
+                Some(c) if must_escape(style, format.multiline, c, rest) => return Err(err_at(i)),
+                Some('\\') if style == Some(Escapes::Toml) && format.multiline && ends_line(&rest[1..]) =>
+                {
+                    decoded.get_or_insert_with(|| String::from(&text[open..i]));
+                    i += rest.len() - rest[1..].trim_start_matches([' ', '\t', '\r', '\n']).len();
+                },
+                Some('\\') if format.escapes =>
+                {
+                    let (c, len) = decode_escape(rest, format.escape_style).ok_or_else(|| err_at(i))?;
+                    decoded.get_or_insert_with(|| String::from(&text[open..i])).push(c);
+                    i += len;
+                },
+                Some(c) =>
+                {
+                    if let Some(s) = decoded.as_mut()
+                    {
+                        s.push(c);
+                    }
+                    i += c.len_utf8();
+                },
+            }
+        }
+    }
+}
+
+// Whether the character at the start of `rest` is a control character that must be escaped
+fn must_escape(style : Option<Escapes>, multiline : bool, c : char, rest : &str) -> bool
+{
+    match style
+    {
+        Some(Escapes::Json) => c < ' ',
+        Some(Escapes::Toml) =>
+        {
+            let newline = c == '\n' || rest.starts_with("\r\n");
+            (c < ' ' || c == '\x7f') && c != '\t' && !(multiline && newline)
+        },
+        _ => false,
+    }
+}
+
+// Whether `text` is only spaces and tabs up to the end of the line
+fn ends_line(text : &str) -> bool
+{
+    let rest = text.trim_start_matches([' ', '\t']);
+    rest.starts_with('\n') || rest.starts_with("\r\n")
+}
+
+// Decodes the escape that `text` begins with, giving the character and the length of the escape
+fn decode_escape(text : &str, escapes : Escapes) -> Option<(char, usize)>
+{
+    let c = text[1..].chars().next()?;
+    let standard = escapes == Escapes::Standard;
+    let simple = match c
+    {
+        'n' => Some('\n'),
+        'r' => Some('\r'),
+        't' => Some('\t'),
+        '\\' | '"' => Some(c),
+        '0' if standard => Some('\0'),
+        '\'' if standard => Some(c),
+        '/' if escapes == Escapes::Json => Some(c),
+        'b' if !standard => Some('\u{8}'),
+        'f' if !standard => Some('\u{c}'),
+        _ => None,
+    };
+    if let Some(s) = simple
+    {
+        return Some((s, 2));
+    }
+
+    match c
+    {
+        'x' if standard => Some((char::from(hex(text.get(2..4)?)? as u8), 4)),
+        'u' if standard && text[2..].starts_with('{') =>
+        {
+            let close = text.find('}')?;
+            let digits = &text[3..close];
+            if digits.is_empty() || digits.len() > 6
+            {
+                return None;
+            }
+            Some((char::from_u32(hex(digits)?)?, close + 1))
+        },
+        'U' if escapes == Escapes::Toml => Some((char::from_u32(hex(text.get(2..10)?)?)?, 10)),
+        'u' =>
+        {
+            let unit = hex(text.get(2..6)?)?;
+            match unit
+            {
+                // TOML only allows escapes of Unicode scalar values, so never a surrogate
+                0xD800..=0xDBFF if escapes != Escapes::Toml =>
+                {
+                    // A high surrogate must be followed by a low surrogate, which together encode a single character
+                    if !text[6..].starts_with("\\u")
+                    {
+                        return None;
+                    }
+                    let low = hex(text.get(8..12)?)?;
+                    if !(0xDC00..=0xDFFF).contains(&low)
+                    {
+                        return None;
+                    }
+                    Some((char::from_u32(0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00))?, 12))
+                },
+                _ => Some((char::from_u32(unit)?, 6)),
+            }
+        },
+        _ => None,
+    }
+}
+
+fn hex(digits : &str) -> Option<u32>
+{
+    if digits.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        u32::from_str_radix(digits, 16).ok()
+    }
+    else
+    {
+        None
+    }
+}
@@ -1,7 +1,49 @@
-mod vfs;
+mod balanced;
+mod char_class;
+mod cst;
+mod csv;
+mod datetime;
+mod derive;
+mod grammar;
+mod grammar_macro;
+mod incremental;
+mod ini;
+mod json;
+mod keywords;
+mod numbers;
+mod paths;
+mod profile;
+mod railroad;
+#[cfg(feature = "regex")]
+mod regex;
+mod strings;
+mod toml;
+mod trace;
+mod trivia;
+mod uri;
 
 use crate::prelude::*;
 
+// The path of a directory under `tests/fixtures`
+fn fixture_dir(dir : &str) -> String { format!("{}/src/volt_parse/tests/fixtures/{dir}", env!("CARGO_MANIFEST_DIR")) }
+
+// The files with the extension in a directory under `tests/fixtures`, as their names without the extension and their
+// bytes, sorted by name
+fn fixtures(dir : &str, extension : &str) -> Vec<(String, Vec<u8>)>
+{
+    let mut files = std::fs::read_dir(fixture_dir(dir))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == extension))
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            (name, std::fs::read(&path).unwrap())
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
 #[test]
 fn then_test()
 {
@@ -135,3 +177,64 @@ fn test_and()
         },)
     );
 }
+
+#[test]
+fn long_repetition_test()
+{
+    // Long enough that recursing once per item would overflow the stack
+    let text = "ab".repeat(200_000);
+    let res = none_or_many(keyword("ab"))(&ParserInput::new(&text)).unwrap();
+    assert_eq!(res.val.len(), 200_000);
+    assert_eq!(res.remainder, "");
+
+    let list = sep_by(ascii_digit(), char_single(','))(&ParserInput::new("1,2,3,")).unwrap();
+    assert_eq!(list.val, vec!['1', '2', '3']);
+    assert_eq!(list.remainder, ",");
+
+    // A repetition of something that matches nothing stops rather than looping forever
+    let res = none_or_many(maybe(char_single('x')))(&ParserInput::new("yyy")).unwrap();
+    assert_eq!(res.val, vec![]);
+    assert_eq!(res.remainder, "yyy");
+}
+
+#[test]
+fn none_or_many_until_test()
+{
+    let until = |text| none_or_many_until(any_char(), keyword(";"), left_right)(&ParserInput::new(text));
+
+    // The end is tried before each item, so the repetition stops at the first end rather than the last
+    let res = until("ab;c;");
+    assert_eq!(
+        res.map(|r| (r.val, r.remainder)),
+        Ok(((vec!['a', 'b'], String::from(";")), "c;"))
+    );
+    assert_eq!(until(";").map(|r| r.val.0), Ok(vec![]));
+    assert_eq!(until("ab").map_err(|e| e.pos), Err(FilePos::new(1, 2)));
+
+    // An item that consumes nothing would never reach the end, so it fails there
+    let res = none_or_many_until(maybe(char_single('x')), keyword(";"), left_right)(&ParserInput::new("xxy;"));
+    assert_eq!(res.map_err(|e| e.pos), Err(FilePos::new(1, 2)));
+}
+
+#[test]
+fn one_or_many_until_test()
+{
+    let until = |text| one_or_many_until(any_char(), keyword(";"), left_right)(&ParserInput::new(text));
+
+    // The end is only tried once there is an item, which may itself look like the end
+    assert_eq!(until(";a;").map(|r| (r.val.0, r.remainder)), Ok((vec![';', 'a'], "")));
+    assert_eq!(until("a;b;").map(|r| (r.val.0, r.remainder)), Ok((vec!['a'], "b;")));
+    assert!(until("").is_err());
+    assert!(until("ab").is_err());
+}
+
+#[test]
+fn consume_chars_until_test()
+{
+    let res = consume_chars_until(keyword("*/"))(&ParserInput::new("a * b */ c */"));
+    assert_eq!(
+        res.map(|r| (r.val, r.remainder)),
+        Ok(((String::from("a * b "), String::from("*/")), " c */"))
+    );
+    assert!(consume_chars_until(keyword("*/"))(&ParserInput::new("never closed")).is_err());
+}
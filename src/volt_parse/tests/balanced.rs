@@ -0,0 +1,105 @@
+use crate::prelude::*;
+
+#[test]
+fn nested_test()
+{
+    let res = nested("/*", "*/")(&ParserInput::new("/* a /* b\n */ c */ d"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       String::from("/* a /* b\n */ c */"),
+            pos :       FilePos {
+                line : 2, column : 8
+            },
+            remainder : " d",
+        })
+    );
+    assert_eq!(
+        nested("/*", "*/")(&ParserInput::new("/* a /* b */")),
+        Err(PErr {
+            pos : FilePos {
+                line : 1, column : 0
+            },
+        })
+    );
+    assert!(nested("/*", "*/")(&ParserInput::new("a /* b */")).is_err());
+}
+
+#[test]
+fn nested_strings_test()
+{
+    let p = nested_with("/*", "*/", &[StringFormat::double()]);
+    let res = p(&ParserInput::new(r#"/* s = "*/ \" /*" */ x"#));
+
+    println!("{:#?}", res);
+
+    assert_eq!(res.map(|r| r.remainder), Ok(" x"));
+    // Without strings, the `*/` within the string closes the comment early
+    assert_eq!(
+        nested("/*", "*/")(&ParserInput::new(r#"/* s = "*/" */"#)).map(|r| r.remainder),
+        Ok(r#"" */"#)
+    );
+    // Unclosed regions fail at the outermost `open`, and unclosed strings where they open
+    let err_pos = |text| p(&ParserInput::new(text)).map_err(|e| e.pos);
+    assert_eq!(err_pos("/* a\n/* b */"), Err(FilePos::new(1, 0)));
+    assert_eq!(err_pos("/* \"a */"), Err(FilePos::new(1, 3)));
+}
+
+#[test]
+fn balanced_test()
+{
+    let brackets = balanced(&[('(', ')'), ('{', '}'), ('[', ']')]);
+    let res = brackets(&ParserInput::new("(a, {b: [c, (d)]}) + e"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       String::from("(a, {b: [c, (d)]})"),
+            pos :       FilePos {
+                line :   1,
+                column : 18,
+            },
+            remainder : " + e",
+        })
+    );
+
+    let err_col = |text| brackets(&ParserInput::new(text)).map_err(|e| e.pos.column);
+    // Unclosed regions fail where the region opens, however deep they are left open
+    assert_eq!(err_col("(a {b} [c"), Err(0));
+    // Mismatched closing brackets fail where they are
+    assert_eq!(err_col("(a {b)}"), Err(5));
+    assert_eq!(err_col("a (b)"), Err(0));
+}
+
+#[test]
+fn balanced_strings_test()
+{
+    let brackets = Brackets::new(&[('(', ')')])
+        .with_strings(StringFormat::double())
+        .with_strings(StringFormat::single());
+    let p = balanced_with(brackets);
+
+    assert_eq!(
+        p(&ParserInput::new(r#"(f(")", '(') + "\")") x"#)).map(|r| r.remainder),
+        Ok(" x")
+    );
+    // Without strings, the bracket within the string closes the region early
+    assert_eq!(
+        balanced(&[('(', ')')])(&ParserInput::new(r#"(")")"#)).map(|r| r.val),
+        Ok(String::from(r#"(")"#))
+    );
+    // An unclosed string within the region fails where the string opens
+    assert_eq!(
+        p(&ParserInput::new("(a, \"b)")),
+        Err(PErr {
+            pos : FilePos {
+                line : 1, column : 4
+            },
+        })
+    );
+}
@@ -0,0 +1,99 @@
+use crate::prelude::*;
+
+#[test]
+fn char_class_set_operations_test()
+{
+    let hex = CharClass::range('0', '9') | CharClass::range('a', 'f') | CharClass::range('A', 'F');
+    let consonant = CharClass::range('a', 'z') - CharClass::chars("aeiou");
+    let hex_letter = hex.clone() & !CharClass::range('0', '9');
+
+    assert!("09afAF".chars().all(|c| hex.contains(c)));
+    assert!(!"gG-".chars().any(|c| hex.contains(c)));
+    assert!(consonant.contains('b') && !consonant.contains('e') && !consonant.contains('B'));
+    assert!(hex_letter.contains('c') && !hex_letter.contains('5'));
+    assert!(CharClass::any().contains('\u{10FFFF}') && !CharClass::none().contains('a'));
+    assert_eq!(!!CharClass::chars("ab"), CharClass::chars("ab"));
+
+    let res = one_or_many(char_class(hex))(&ParserInput::new("1fAbg"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       vec!['1', 'f', 'A', 'b'],
+            pos :       FilePos {
+                line : 1, column : 4
+            },
+            remainder : "g",
+        })
+    );
+}
+
+#[test]
+fn char_range_none_of_test()
+{
+    let res = then(
+        one_or_many(char_range('a', 'z')),
+        one_or_many(none_of("\"\n")),
+        left_right,
+    )(&ParserInput::new("abcXYZ é\"rest"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       ("abc".chars().collect(), "XYZ é".chars().collect()),
+            pos :       FilePos {
+                line : 1, column : 8
+            },
+            remainder : "\"rest",
+        })
+    );
+    assert!(none_of("\"\n")(&ParserInput::new("\n")).is_err());
+    assert!(none_of("\"\n")(&ParserInput::new("")).is_err());
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn char_class_unicode_test()
+{
+    let upper = CharClass::Category(GeneralCategory::UppercaseLetter);
+    assert!(upper.contains('A') && upper.contains('Ж') && !upper.contains('a'));
+
+    let greek = CharClass::Script(Script::Greek);
+    assert!(greek.contains('λ') && !greek.contains('l'));
+
+    let greek_lower = greek & CharClass::Category(GeneralCategory::LowercaseLetter);
+    let res = one_or_many(char_class(greek_lower))(&ParserInput::new("αβΓ"));
+    assert_eq!(res.map(|r| r.val), Ok(vec!['α', 'β']));
+
+    assert!(CharClass::XidStart.contains('ä') && !CharClass::XidStart.contains('1'));
+    assert!(CharClass::XidContinue.contains('1') && !CharClass::XidContinue.contains('-'));
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn unicode_identifier_test()
+{
+    let res = sep_by(unicode_identifier(), char_single(' '))(&ParserInput::new("naïve Δx 変数_1 9lives"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       vec![String::from("naïve"), String::from("Δx"), String::from("変数_1")],
+            pos :       FilePos {
+                line :   1,
+                column : 13,
+            },
+            remainder : " 9lives",
+        })
+    );
+
+    // Underscores and digits may continue an identifier but not start one
+    assert!(unicode_identifier()(&ParserInput::new("_x")).is_err());
+    assert!(unicode_identifier()(&ParserInput::new("1x")).is_err());
+}
@@ -0,0 +1,211 @@
+use crate::prelude::*;
+
+const CONFIG : &str = "# settings\nname = volt\n\n  size=  12 # bytes\nlist = a, b,c\n";
+
+fn ident<'a>() -> impl Parser<'a, CstToken> { leaf("ident", one_or_many(or(ascii_alphanumeric(), char_single('_')))) }
+
+fn comment<'a>() -> impl Parser<'a, CstToken>
+{
+    leaf(
+        "comment",
+        then(char_single('#'), none_or_many(read_char_f(|c| c != '\n')), take_left),
+    )
+}
+
+fn ws<'a>() -> impl Parser<'a, Vec<char>> { none_or_many(char_in_str(" \t")) }
+
+fn value<'a>() -> impl Parser<'a, CstNode>
+{
+    node(
+        "value",
+        sep_by1(ident(), then(ws(), then(comma(), ws(), take_left), take_right)),
+    )
+}
+
+fn entry<'a>() -> impl Parser<'a, CstNode>
+{
+    node(
+        "entry",
+        then(
+            then(
+                ident(),
+                then(ws(), then(char_single('='), ws(), take_left), take_right),
+                take_left,
+            ),
+            value(),
+            take_right,
+        ),
+    )
+}
+
+fn line<'a>() -> impl Parser<'a, Option<CstNode>>
+{
+    then(
+        ws(),
+        then(
+            maybe(entry()),
+            then(ws(), then(maybe(comment()), newline(), take_left), take_left),
+            take_left,
+        ),
+        take_right,
+    )
+}
+
+fn config<'a>() -> impl Parser<'a, CstNode> { node("config", all(none_or_many(line()))) }
+
+#[test]
+fn cst_round_trip_test()
+{
+    let tree = config()(&ParserInput::new(CONFIG)).unwrap().val;
+
+    println!("{}", tree.dump());
+
+    assert_eq!(tree.text(), CONFIG);
+    assert_eq!(tree.to_string(), CONFIG);
+    assert_eq!(tree.len(), CONFIG.len());
+    assert_eq!(tree.span, Span::new(FilePos::new(1, 0), FilePos::new(6, 0)));
+    assert_eq!(tree.nodes().count(), 3);
+
+    let tokens = tree
+        .tokens()
+        .iter()
+        .map(|t| (t.kind.as_str(), t.text.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tokens,
+        vec![
+            ("comment", "# settings"),
+            ("whitespace", "\n"),
+            ("ident", "name"),
+            ("whitespace", " "),
+            ("text", "="),
+            ("whitespace", " "),
+            ("ident", "volt"),
+            ("whitespace", "\n\n  "),
+            ("ident", "size"),
+            ("text", "="),
+            ("whitespace", "  "),
+            ("ident", "12"),
+            ("whitespace", " "),
+            ("comment", "# bytes"),
+            ("whitespace", "\n"),
+            ("ident", "list"),
+            ("whitespace", " "),
+            ("text", "="),
+            ("whitespace", " "),
+            ("ident", "a"),
+            ("text", ","),
+            ("whitespace", " "),
+            ("ident", "b"),
+            ("text", ","),
+            ("ident", "c"),
+            ("whitespace", "\n"),
+        ]
+    );
+
+    let size = tree.tokens().into_iter().find(|t| t.text == "size").unwrap().clone();
+    assert_eq!(size.span, Span::new(FilePos::new(4, 2), FilePos::new(4, 6)));
+}
+
+#[test]
+fn cst_backtracking_test()
+{
+    // The first alternative matches an ident before failing, which must not leave a stale child behind
+    let p = node(
+        "pair",
+        or(
+            then(ident(), char_single('!'), take_left),
+            then(
+                leaf("number", one_or_many(ascii_digit())),
+                leaf("suffix", one_or_many(ascii_alpha())),
+                take_left,
+            ),
+        ),
+    );
+    let tree = p(&ParserInput::new("12ab")).unwrap().val;
+
+    println!("{}", tree.dump());
+
+    assert_eq!(
+        tree.children.iter().map(|c| (c.kind(), c.len())).collect::<Vec<_>>(),
+        vec![("number", 2), ("suffix", 2)]
+    );
+}
+
+#[test]
+fn cst_failed_alternative_test()
+{
+    // The failed alternative records a `kw` token where the successful one records nothing
+    let fail = |ind : &ParserInput<'static>| -> POut<'static, String> {
+        Err(PErr {
+            pos : ind.pos
+        })
+    };
+    let p = node(
+        "n",
+        or(
+            then(mod_val(leaf("kw", keyword("if")), |t| t.text), fail, take_left),
+            keyword("if"),
+        ),
+    );
+    let tree = p(&ParserInput::new("if")).unwrap().val;
+
+    println!("{}", tree.dump());
+
+    assert_eq!(
+        tree.children.iter().map(|c| c.kind()).collect::<Vec<_>>(),
+        vec![TEXT_KIND]
+    );
+
+    // Nor may lookahead, or a repetition that fails partway through an item
+    let p = node(
+        "n",
+        then(
+            then(
+                no_consume(leaf("peek", keyword("a"))),
+                none_or_many(then(leaf("item", keyword("a")), keyword(","), take_left)),
+                take_right,
+            ),
+            not(
+                then(mod_val(leaf("stop", keyword("a")), |t| t.text), keyword("!"), take_left),
+                String::new,
+            ),
+            take_left,
+        ),
+    );
+    let tree = p(&ParserInput::new("a,a,a")).unwrap().val;
+
+    println!("{}", tree.dump());
+
+    assert_eq!(
+        tree.children.iter().map(|c| (c.kind(), c.len())).collect::<Vec<_>>(),
+        vec![("item", 1), (TEXT_KIND, 1), ("item", 1), (TEXT_KIND, 1)]
+    );
+}
+
+#[test]
+fn cst_failed_first_branch_test()
+{
+    // `maybe` tries its parser first, so a `num` it records before failing must be rolled back
+    let num = then(leaf("num", keyword("1")), keyword("Q"), left_right);
+    let p = node(
+        "root",
+        then(maybe(mod_val(num.clone(), |_| ())), keyword("12"), left_right),
+    );
+    let tree = p(&ParserInput::new("12")).unwrap().val;
+
+    println!("{}", tree.dump());
+
+    assert_eq!(
+        tree.children.iter().map(|c| (c.kind(), c.len())).collect::<Vec<_>>(),
+        vec![(TEXT_KIND, 2)]
+    );
+
+    // And the same for the first of several alternatives of different types
+    let p = node("root", or2(num, leaf("all", keyword("12"))));
+    let tree = p(&ParserInput::new("12")).unwrap().val;
+    assert_eq!(
+        tree.children.iter().map(|c| (c.kind(), c.len())).collect::<Vec<_>>(),
+        vec![("all", 2)]
+    );
+}
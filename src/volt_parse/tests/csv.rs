@@ -0,0 +1,128 @@
+use crate::prelude::*;
+use crate::volt_parse::formats::csv::*;
+
+fn vals(record : &CsvRecord) -> Vec<&str> { record.iter().map(|f| f.val.as_str()).collect() }
+
+#[test]
+fn csv_quoting_test()
+{
+    let text = "name,notes,qty\r\nbolt,\"says \"\"hi\"\"\",4\nnut,\"two\nlines, one comma\",\n,,\n";
+    let csv = parse_csv(text, CsvFormat::default().with_header()).unwrap();
+
+    println!("{:#?}", csv);
+
+    assert_eq!(vals(csv.headers.as_ref().unwrap()), vec!["name", "notes", "qty"]);
+    assert_eq!(csv.column("qty"), Some(2));
+    assert_eq!(csv.records.len(), 3);
+    assert_eq!(vals(&csv.records[0]), vec!["bolt", "says \"hi\"", "4"]);
+    assert_eq!(vals(&csv.records[1]), vec!["nut", "two\nlines, one comma", ""]);
+    assert_eq!(vals(&csv.records[2]), vec!["", "", ""]);
+
+    // Positions continue correctly after a field spanning lines
+    assert_eq!(
+        csv.records[1][1].span,
+        Span::new(FilePos::new(3, 4), FilePos::new(4, 17))
+    );
+    assert_eq!(
+        csv.records[1][2].span,
+        Span::new(FilePos::new(4, 18), FilePos::new(4, 18))
+    );
+    assert_eq!(
+        csv.records[2][1].span,
+        Span::new(FilePos::new(5, 1), FilePos::new(5, 1))
+    );
+}
+
+#[test]
+fn csv_format_test()
+{
+    let tsv = parse_csv("a\tb,c\t'd\te'\n", CsvFormat::tsv().with_quote(Some('\''))).unwrap();
+    assert_eq!(vals(&tsv.records[0]), vec!["a", "b,c", "d\te"]);
+
+    let escaped = parse_csv(r#""a \"b\" \\ c",d"#, CsvFormat::default().with_escape('\\')).unwrap();
+    assert_eq!(vals(&escaped.records[0]), vec![r#"a "b" \ c"#, "d"]);
+
+    let unquoted = parse_csv("\"a\",b", CsvFormat::default().with_quote(None)).unwrap();
+    assert_eq!(vals(&unquoted.records[0]), vec!["\"a\"", "b"]);
+
+    let semicolons = csv_record(CsvFormat::default().with_delimiter(';'))(&ParserInput::new("1;2,5;3\nrest"));
+    assert_eq!(
+        semicolons.map(|r| (vals(&r.val).join("|"), r.remainder)),
+        Ok((String::from("1|2,5|3"), "\nrest"))
+    );
+}
+
+#[test]
+fn csv_reader_test()
+{
+    let text = "id,name\n1,a\n2,\"b\n3,c\n";
+    let mut reader = CsvReader::new(text, CsvFormat::default().with_header()).unwrap();
+
+    assert_eq!(vals(reader.headers().unwrap()), vec!["id", "name"]);
+    assert_eq!(
+        reader.next().map(|r| r.map(|r| vals(&r).join("|"))),
+        Some(Ok(String::from("1|a")))
+    );
+    // An unclosed quote fails where it opens, ending the records
+    assert_eq!(
+        reader.next(),
+        Some(Err(PErr {
+            pos : FilePos::new(3, 2),
+        }))
+    );
+    assert_eq!(reader.next(), None);
+
+    // Text after a closing quote fails where it begins
+    assert_eq!(
+        parse_csv("a,\"b\"c", CsvFormat::default()),
+        Err(PErr {
+            pos : FilePos::new(1, 5),
+        })
+    );
+    assert_eq!(parse_csv("", CsvFormat::default()).map(|c| c.records.len()), Ok(0));
+}
+
+#[test]
+fn csv_line_end_test()
+{
+    let records = |text| {
+        parse_csv(text, CsvFormat::default()).map(|c| c.records.iter().map(|r| vals(r).join("|")).collect::<Vec<_>>())
+    };
+
+    // Blank lines are skipped wherever they are, but a line of empty fields is still a record
+    assert_eq!(
+        records("a,b\n\nc,d\n"),
+        Ok(vec![String::from("a|b"), String::from("c|d")])
+    );
+    assert_eq!(
+        records("\r\n\na\n,\n\n"),
+        Ok(vec![String::from("a"), String::from("|")])
+    );
+    // A lone carriage return is not a line end, and fails where it is rather than being kept in the field
+    assert_eq!(
+        parse_csv("a,b\rc\r", CsvFormat::default()),
+        Err(PErr {
+            pos : FilePos::new(1, 3),
+        })
+    );
+    assert_eq!(
+        parse_csv("a\nb\r", CsvFormat::default()),
+        Err(PErr {
+            pos : FilePos::new(2, 1),
+        })
+    );
+    // Within quotes it is part of the field, and the fields after it are still on the right line
+    let quoted = parse_csv("\"a\rb\"\r\n\r\nc", CsvFormat::default()).unwrap();
+    assert_eq!(quoted.records[0][0].val, "a\rb");
+    assert_eq!(
+        quoted.records[1][0].span,
+        Span::new(FilePos::new(3, 0), FilePos::new(3, 1))
+    );
+
+    let csv = parse_csv("\nid\n\n1\n", CsvFormat::default().with_header()).unwrap();
+    assert_eq!(vals(csv.headers.as_ref().unwrap()), vec!["id"]);
+    assert_eq!(
+        csv.records[0][0].span,
+        Span::new(FilePos::new(4, 0), FilePos::new(4, 1))
+    );
+}
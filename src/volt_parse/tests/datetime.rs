@@ -0,0 +1,183 @@
+use crate::prelude::*;
+use crate::volt_parse::formats::datetime::*;
+
+fn date(year : u16, month : u8, day : u8) -> Date
+{
+    Date {
+        year,
+        month,
+        day,
+    }
+}
+
+fn err_at<T : PResData>(res : POut<'_, T>) -> usize { res.err().unwrap().pos.column }
+
+#[test]
+fn rfc3339_test()
+{
+    let res = rfc3339()(&ParserInput::new("1985-04-12T23:20:50.52Z"));
+
+    println!("{:#?}", res);
+
+    let dt = res.unwrap().val;
+    assert_eq!(dt.date, date(1985, 4, 12));
+    assert_eq!(dt.time.nanosecond, 520_000_000);
+    assert_eq!(dt.offset, Some(Offset::Utc));
+    assert_eq!(dt.to_string(), "1985-04-12T23:20:50.52Z");
+
+    // Leap years follow the Gregorian rules
+    assert!(rfc3339()(&ParserInput::new("2000-02-29T00:00:00Z")).is_ok());
+    assert!(rfc3339()(&ParserInput::new("2024-02-29T00:00:00Z")).is_ok());
+    assert_eq!(err_at(rfc3339()(&ParserInput::new("1900-02-29T00:00:00Z"))), 8);
+    assert_eq!(err_at(rfc3339()(&ParserInput::new("2023-04-31T00:00:00Z"))), 8);
+    assert_eq!(err_at(rfc3339()(&ParserInput::new("2023-13-01T00:00:00Z"))), 5);
+    assert_eq!(err_at(rfc3339()(&ParserInput::new("2023-01-01T24:00:00Z"))), 11);
+    // A missing offset fails where it should begin, and an offset out of range fails at its field
+    assert_eq!(err_at(rfc3339()(&ParserInput::new("2024-03-15T10:00:00"))), 19);
+    assert_eq!(err_at(rfc3339()(&ParserInput::new("2024-03-15T10:00:00+25:00"))), 20);
+    assert_eq!(err_at(rfc3339()(&ParserInput::new("2024-03-15T10:00:00+05:60"))), 23);
+
+    let stamp = rfc3339()(&ParserInput::new("2001-09-09T01:46:40Z")).unwrap().val;
+    assert_eq!(stamp.unix_timestamp(), Some(1_000_000_000));
+    let shifted = rfc3339()(&ParserInput::new("2001-09-09T03:46:40+02:00")).unwrap().val;
+    assert_eq!(shifted.unix_timestamp(), Some(1_000_000_000));
+    let before = rfc3339()(&ParserInput::new("1969-12-31T23:59:59Z")).unwrap().val;
+    assert_eq!(before.unix_timestamp(), Some(-1));
+}
+
+#[test]
+fn iso8601_date_test()
+{
+    let parse = |text| iso8601_date()(&ParserInput::new(text)).map(|res| res.val);
+
+    assert_eq!(parse("2024-03-15"), Ok(date(2024, 3, 15)));
+    assert_eq!(parse("20240315"), Ok(date(2024, 3, 15)));
+
+    // Ordinal dates, where only leap years have a 366th day
+    assert_eq!(parse("2024-075"), Ok(date(2024, 3, 15)));
+    assert_eq!(parse("2024366"), Ok(date(2024, 12, 31)));
+    assert_eq!(err_at(iso8601_date()(&ParserInput::new("2023-366"))), 5);
+
+    // Week dates, where the first week is the one with the year's first Thursday
+    assert_eq!(parse("2009-W01-1"), Ok(date(2008, 12, 29)));
+    assert_eq!(parse("2009W537"), Ok(date(2010, 1, 3)));
+    assert_eq!(parse("2020-W53-5"), Ok(date(2021, 1, 1)));
+    assert_eq!(err_at(iso8601_date()(&ParserInput::new("2021-W53-1"))), 6);
+    assert_eq!(err_at(iso8601_date()(&ParserInput::new("2021-W01-8"))), 9);
+
+    // Hyphens are used for every field or none
+    assert!(parse("2024-0315").is_err());
+    assert!(parse("202403-15").is_err());
+
+    let round_trip = date(2008, 12, 29);
+    assert_eq!(round_trip.weekday(), 1);
+    assert_eq!(round_trip.ordinal(), 364);
+    assert_eq!(Date::from_week_date(2009, 1, 1), Some(round_trip));
+    assert_eq!(Date::from_ordinal(2008, 364), Some(round_trip));
+    assert_eq!(weeks_in_year(2009), 53);
+    assert_eq!(weeks_in_year(2021), 52);
+}
+
+#[test]
+fn iso8601_test()
+{
+    let res = iso8601()(&ParserInput::new("2024-W11-5T14:30:15,5+05:30"));
+
+    println!("{:#?}", res);
+
+    let dt = res.unwrap().val;
+    assert_eq!(dt.date, date(2024, 3, 15));
+    assert_eq!(
+        dt.time,
+        Time {
+            hour :       14,
+            minute :     30,
+            second :     15,
+            nanosecond : 500_000_000,
+        }
+    );
+    assert_eq!(dt.offset, Some(Offset::Minutes(330)));
+
+    let basic = iso8601()(&ParserInput::new("20240315T1430-08")).unwrap().val;
+    assert_eq!(basic.time.second, 0);
+    assert_eq!(basic.offset, Some(Offset::Minutes(-480)));
+
+    let local = iso8601()(&ParserInput::new("2024-075T00:00")).unwrap().val;
+    assert_eq!(local.offset, None);
+
+    assert_eq!(err_at(iso8601()(&ParserInput::new("2024-03-15T14:60"))), 14);
+}
+
+#[test]
+fn iso8601_duration_test()
+{
+    let res = iso8601_duration()(&ParserInput::new("P3Y6M4DT12H30M5S"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res.unwrap().val,
+        Duration {
+            years : 3,
+            months : 6,
+            days : 4,
+            hours : 12,
+            minutes : 30,
+            seconds : 5,
+            ..Duration::default()
+        }
+    );
+
+    let parse = |text| iso8601_duration()(&ParserInput::new(text)).map(|res| res.val);
+    assert_eq!(parse("P2W").unwrap().weeks, 2);
+    assert_eq!(parse("PT0,25S").unwrap().nanoseconds, 250_000_000);
+    assert_eq!(parse("PT36H").unwrap().to_string(), "PT36H");
+    assert_eq!(parse("P1DT0.5S").unwrap().to_string(), "P1DT0.5S");
+    assert_eq!(Duration::default().to_string(), "PT0S");
+
+    // Nothing after `P` or `T`
+    assert_eq!(err_at(iso8601_duration()(&ParserInput::new("P"))), 1);
+    assert_eq!(err_at(iso8601_duration()(&ParserInput::new("P1DT"))), 4);
+    // Out of order, and a fraction on a component other than the seconds
+    assert_eq!(err_at(iso8601_duration()(&ParserInput::new("P1D2Y"))), 4);
+    assert_eq!(err_at(iso8601_duration()(&ParserInput::new("PT1.5M"))), 5);
+}
+
+#[test]
+fn rfc2822_test()
+{
+    let res = rfc2822()(&ParserInput::new("Fri, 21 Nov 1997 09:55:06 -0600"));
+
+    println!("{:#?}", res);
+
+    let dt = res.unwrap().val;
+    assert_eq!(dt.date, date(1997, 11, 21));
+    assert_eq!(dt.time.second, 6);
+    assert_eq!(dt.offset, Some(Offset::Minutes(-360)));
+
+    let parse = |text| rfc2822()(&ParserInput::new(text)).map(|res| res.val);
+
+    let obsolete = parse("21 Nov 97 09:55:06 GMT").unwrap();
+    assert_eq!(obsolete.date, date(1997, 11, 21));
+    assert_eq!(obsolete.offset, Some(Offset::Minutes(0)));
+    assert_eq!(parse("1 jan 05 00:00 EST").unwrap().date, date(2005, 1, 1));
+    assert_eq!(parse("1 Jan 105 00:00 z").unwrap().date.year, 2005);
+
+    let folded = parse(
+        "Thu,\r\n      13\r\n        Feb\r\n          1969\r\n      23:32\r\n               -0330 (Newfoundland Time)",
+    )
+    .unwrap();
+    assert_eq!(folded.date, date(1969, 2, 13));
+    assert_eq!(folded.offset, Some(Offset::Minutes(-210)));
+    let commented = parse("Tue, 1 Jul 2003 10:52:37 (a (nested) note) +0200").unwrap();
+    assert_eq!(commented.time.hour, 10);
+
+    // The day of the week must match the date, and the day must be within the month
+    assert_eq!(
+        err_at(rfc2822()(&ParserInput::new("Sat, 21 Nov 1997 09:55:06 -0600"))),
+        0
+    );
+    assert_eq!(err_at(rfc2822()(&ParserInput::new("31 Apr 2003 00:00 +0000"))), 0);
+    assert_eq!(err_at(rfc2822()(&ParserInput::new("1 Foo 2003 00:00 +0000"))), 2);
+    assert_eq!(err_at(rfc2822()(&ParserInput::new("1 Jan 2003 00:00 XYZ"))), 17);
+}
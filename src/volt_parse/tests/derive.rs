@@ -0,0 +1,141 @@
+// The derive macro refers to `volt_parse::..`, which is this crate
+use crate as volt_parse;
+use crate::prelude::*;
+use volt_parse_macros::VoltParse;
+
+fn ident<'a>() -> impl Parser<'a, String> { mod_val(one_or_many(ascii_alpha()), |cs| cs.into_iter().collect()) }
+
+fn number<'a>() -> impl Parser<'a, u32>
+{
+    mod_val(one_or_many(ascii_digit()), |cs| {
+        cs.into_iter().fold(0, |n, c| n * 10 + c.to_digit(10).unwrap())
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, VoltParse)]
+enum Piece
+{
+    #[keyword("..")]
+    Up,
+    #[keyword(".")]
+    Current,
+    Name(#[parser(ident)] String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, VoltParse)]
+struct Num(#[parser(number)] u32);
+
+#[derive(Debug, Clone, PartialEq, Eq, VoltParse)]
+#[skip_ws]
+#[keyword("let")]
+struct Let
+{
+    #[parser(ident)]
+    name :   String,
+    #[keyword("=")]
+    _eq :    String,
+    #[sep(",")]
+    values : Vec<Num>,
+    #[keyword(";")]
+    _end :   String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, VoltParse)]
+enum Expr
+{
+    Num(Num),
+    #[keyword("(")]
+    Group(Box<Expr>, #[keyword(")")] String),
+    #[keyword("-")]
+    Neg
+    {
+        inner : Option<Box<Expr>>,
+    },
+}
+
+fn digit_leaf<'a>() -> impl Parser<'a, String> { mod_val(leaf("digit", ascii_digit()), |t| t.text) }
+
+#[derive(Debug, Clone, PartialEq, Eq, VoltParse)]
+enum Marked
+{
+    Digit(#[parser(digit_leaf)] String, #[keyword("!")] String),
+    Number(#[parser(number)] u32),
+}
+
+#[test]
+fn derive_enum_test()
+{
+    let res = none_or_many(Piece::parser())(&ParserInput::new("..abc."));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       vec![Piece::Up, Piece::Name(String::from("abc")), Piece::Current],
+            pos :       FilePos {
+                line : 1, column : 6
+            },
+            remainder : "",
+        })
+    );
+}
+
+#[test]
+fn derive_struct_sep_skip_ws_test()
+{
+    let res = Let::parser()(&ParserInput::new("  let x =1 ,  2,3 ;rest"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       Let {
+                name :   String::from("x"),
+                _eq :    String::from("="),
+                values : vec![Num(1), Num(2), Num(3)],
+                _end :   String::from(";"),
+            },
+            pos :       FilePos {
+                line :   1,
+                column : 19,
+            },
+            remainder : "rest",
+        })
+    );
+
+    assert!(Let::parser()(&ParserInput::new("let x = 1 2;")).is_err());
+}
+
+#[test]
+fn derive_recursive_test()
+{
+    let res = all(Expr::parser())(&ParserInput::new("((-(7)))"));
+
+    println!("{:#?}", res);
+
+    let neg = Expr::Neg {
+        inner : Some(Box::new(Expr::Group(Box::new(Expr::Num(Num(7))), String::from(")")))),
+    };
+    let expected = Expr::Group(
+        Box::new(Expr::Group(Box::new(neg), String::from(")"))),
+        String::from(")"),
+    );
+    assert_eq!(res.map(|r| r.val), Ok(expected));
+}
+
+#[test]
+fn derive_enum_cst_test()
+{
+    // The `Digit` variant records a `digit` leaf before failing, which must not be left in the tree of `Number`
+    let res = node("marked", Marked::parser())(&ParserInput::new("12"));
+
+    println!("{:#?}", res);
+
+    let tree = res.unwrap().val;
+    assert_eq!(
+        tree.children.iter().map(|c| (c.kind(), c.len())).collect::<Vec<_>>(),
+        vec![(TEXT_KIND, 2)]
+    );
+}
@@ -0,0 +1,313 @@
+use crate::{grammar::*, prelude::*};
+
+const ARITHMETIC : &str = r#"
+    # A tiny calculator grammar
+    Expr   <- Sum !.
+    Sum    <- Product (("+" / "-") Product)*
+    Product = Value (('*' | '/') Value)* ;
+    Value  <- Number / "(" Sum ")"
+    Number <- [0-9]+ ("." [0-9]+)?
+"#;
+
+#[test]
+fn grammar_from_peg_test()
+{
+    let grammar = Grammar::from_peg(ARITHMETIC).unwrap();
+
+    assert_eq!(
+        grammar.rules().iter().map(|r| r.name.as_str()).collect::<Vec<_>>(),
+        vec!["Expr", "Sum", "Product", "Value", "Number"]
+    );
+    assert_eq!(
+        grammar.rule("Number").unwrap().expr,
+        Expr::Seq(vec![
+            Expr::OneOrMore(Box::new(Expr::Class {
+                ranges :  vec![('0', '9')],
+                negated : false,
+            })),
+            Expr::Optional(Box::new(Expr::Seq(vec![
+                Expr::Literal(String::from(".")),
+                Expr::OneOrMore(Box::new(Expr::Class {
+                    ranges :  vec![('0', '9')],
+                    negated : false,
+                })),
+            ]))),
+        ])
+    );
+}
+
+#[test]
+fn grammar_parse_tree_test()
+{
+    let grammar = Grammar::from_peg(ARITHMETIC).unwrap();
+    let res = grammar.parse("2*(3+4.5)");
+
+    println!("{:#?}", res);
+
+    let tree = res.unwrap().val;
+    assert_eq!(tree.rule, "Expr");
+    assert_eq!(tree.text, "2*(3+4.5)");
+    assert_eq!(tree.span, Span::new(FilePos::new(1, 0), FilePos::new(1, 9)));
+
+    let inner = tree.find("Value").unwrap().children[0].clone();
+    assert_eq!(inner.rule, "Number");
+    assert_eq!(inner.text, "2");
+
+    let numbers = tree.children[0].children[0].children[1].children[0]
+        .children
+        .iter()
+        .map(|n| n.text.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(numbers, vec!["3", "4.5"]);
+}
+
+#[test]
+fn grammar_predicates_and_classes_test()
+{
+    let grammar = Grammar::from_peg(
+        r#"
+        Words   <- Word (" " Word)*
+        Word    <- !Keyword [a-zA-Z_\u{e9}]+ / Keyword
+        Keyword <- "if" ![a-z]
+        Line    <- [^\n]* "\n"
+        "#,
+    )
+    .unwrap();
+
+    let tree = grammar.parse("ifs café if").unwrap().val;
+    assert_eq!(
+        tree.children
+            .iter()
+            .map(|n| (n.text.as_str(), n.children.len()))
+            .collect::<Vec<_>>(),
+        vec![("ifs", 0), ("café", 0), ("if", 1)]
+    );
+
+    let line = grammar.parser("Line").unwrap()(&ParserInput::new("one\ntwo"));
+    assert_eq!(
+        line.map(|r| (r.val.span, r.remainder)),
+        Ok((Span::new(FilePos::new(1, 0), FilePos::new(2, 0)), "two"))
+    );
+}
+
+#[test]
+fn grammar_errors_test()
+{
+    let grammar = Grammar::from_peg(ARITHMETIC).unwrap();
+    assert_eq!(
+        grammar.parse("1+(2*3"),
+        Err(PErr {
+            pos : FilePos::new(1, 6),
+        })
+    );
+
+    assert_eq!(
+        Grammar::from_peg("A <- B"),
+        Err(GrammarError::UndefinedRule {
+            name :          String::from("B"),
+            referenced_by : String::from("A"),
+        })
+    );
+    assert_eq!(
+        Grammar::from_peg("A <- 'a'\nA <- 'b'"),
+        Err(GrammarError::DuplicateRule(String::from("A")))
+    );
+    assert!(matches!(Grammar::from_peg("A <- ('a'"), Err(GrammarError::Syntax(_))));
+    assert!(matches!(
+        grammar.parser("Missing"),
+        Err(GrammarError::UnknownStartRule(name)) if name == "Missing"
+    ));
+}
+
+#[test]
+fn grammar_left_recursion_test()
+{
+    use crate::grammar::build::*;
+
+    // The recursive call fails rather than overflowing the stack, leaving only the other alternative
+    let grammar = Grammar::new(vec![define("A", or(then(rule("A"), keyword("x")), keyword("x")))]).unwrap();
+    let res = grammar.parse("xx");
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Err(PErr {
+            pos : FilePos::new(1, 1),
+        })
+    );
+    assert_eq!(grammar.parse("x").unwrap().val.text, "x");
+
+    let indirect = Grammar::from_peg("A <- B 'x' / 'y'\nB <- A? 'z'").unwrap();
+    assert!(indirect.parse("zx").is_ok());
+}
+
+fn built_arithmetic() -> Grammar
+{
+    use crate::grammar::build::*;
+
+    let digits = || one_or_many(char_range('0', '9'));
+    Grammar::new(vec![
+        define("Expr", then(rule("Sum"), not(any_char()))),
+        define(
+            "Sum",
+            then(
+                rule("Product"),
+                none_or_many(then(or(keyword("+"), keyword("-")), rule("Product"))),
+            ),
+        ),
+        define(
+            "Product",
+            then(
+                rule("Value"),
+                none_or_many(then(or(keyword("*"), keyword("/")), rule("Value"))),
+            ),
+        ),
+        define(
+            "Value",
+            or(rule("Number"), seq(vec![keyword("("), rule("Sum"), keyword(")")])),
+        ),
+        define("Number", then(digits(), maybe(then(keyword("."), digits())))),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn grammar_build_test()
+{
+    let built = built_arithmetic();
+    assert_eq!(Grammar::from_peg(ARITHMETIC), Ok(built.clone()));
+
+    assert_eq!(built.parse("1+2*3").map(|r| r.val.text), Ok(String::from("1+2*3")));
+}
+
+#[test]
+fn grammar_display_test()
+{
+    let grammar = built_arithmetic();
+
+    assert_eq!(
+        grammar.to_string(),
+        [
+            "Expr <- Sum !.",
+            "Sum <- Product ((\"+\" / \"-\") Product)*",
+            "Product <- Value ((\"*\" / \"/\") Value)*",
+            "Value <- Number / \"(\" Sum \")\"",
+            "Number <- [0-9]+ (\".\" [0-9]+)?",
+            "",
+        ]
+        .join("\n")
+    );
+    assert_eq!(Grammar::from_peg(&grammar.to_string()), Ok(grammar));
+
+    let tricky = Grammar::from_peg(r#"A <- ("a" / "b")? &(. .) [\^\]\-a-c] "\"\\\n" ("x" / ()) "#).unwrap();
+    assert_eq!(Grammar::from_peg(&tricky.to_string()), Ok(tricky));
+}
+
+#[test]
+fn grammar_analysis_test()
+{
+    let grammar = Grammar::from_peg(
+        r##"
+        List  <- "[" Items? "]" Extra
+        Items <- Item ("," Item)*
+        Item  <- [a-c] / [x-z] / Space* "#"
+        Space <- " "
+        Extra <- [^\u{0}-y]?
+        "##,
+    )
+    .unwrap();
+
+    let sets = grammar.first_sets();
+    assert_eq!(sets["List"], FirstSet::chars(vec![('[', '[')]));
+    assert_eq!(
+        sets["Item"],
+        FirstSet::chars(vec![(' ', ' '), ('#', '#'), ('a', 'c'), ('x', 'z')])
+    );
+    assert_eq!(sets["Items"], sets["Item"]);
+    assert_eq!(
+        sets["Extra"],
+        FirstSet {
+            ranges :   vec![('z', char::MAX)],
+            nullable : true,
+        }
+    );
+    assert!(sets["Item"].contains('y'));
+    assert!(!sets["Item"].contains('w'));
+    assert!(sets["Item"].overlaps(&FirstSet::chars(vec![('c', 'd')])));
+
+    assert_eq!(grammar.nullable_rules().into_iter().collect::<Vec<_>>(), vec!["Extra"]);
+    assert_eq!(grammar.references("List"), vec!["Items", "Extra"]);
+    assert_eq!(grammar.referenced_by("Item"), vec!["Items"]);
+    assert_eq!(grammar.reachable().len(), 5);
+
+    let docs = grammar.to_markdown();
+    assert!(docs.starts_with("## `List`\n\n```peg\nList <- \"[\" Items? \"]\" Extra\n```\n\n## `Items`"));
+    assert!(
+        docs.contains("## `Extra`\n\n```peg\nExtra <- [^\\0-y]?\n```\n\nUsed by: `List`\n\nMay match empty input.\n\n")
+    );
+}
+
+#[test]
+fn grammar_lint_test()
+{
+    assert_eq!(Grammar::from_peg(ARITHMETIC).unwrap().lint(), vec![]);
+
+    let grammar = Grammar::from_peg(
+        r#"
+        Start   <- Keyword Ident? Blank List Expr
+        Keyword <- "in" / "int" / [a-z] "x" / "if"
+        Ident   <- [a-z]+ / "x"
+        Blank   <- (" "?)*
+        List    <- Item ("," Item)*
+        Item    <- "a"? / "b"
+        Expr    <- Sum
+        Sum     <- Product "+" Sum / Product
+        Product <- Atom? Sum "*" / Atom
+        Atom    <- [0-9]
+        Unused  <- "unused"
+        "#,
+    )
+    .unwrap();
+    let lints = grammar.lint();
+
+    println!("{}", lints.iter().map(|l| l.to_string()).collect::<Vec<_>>().join("\n"));
+
+    assert_eq!(
+        lints,
+        vec![
+            Lint::ShadowedAlternative {
+                rule :        String::from("Keyword"),
+                alternative : String::from("\"int\""),
+                shadowed_by : String::from("\"in\""),
+            },
+            Lint::ShadowedAlternative {
+                rule :        String::from("Ident"),
+                alternative : String::from("\"x\""),
+                shadowed_by : String::from("[a-z]+"),
+            },
+            Lint::NullableRepetition {
+                rule : String::from("Blank"),
+                expr : String::from("(\" \"?)*"),
+            },
+            Lint::ShadowedAlternative {
+                rule :        String::from("Item"),
+                alternative : String::from("\"b\""),
+                shadowed_by : String::from("\"a\"?"),
+            },
+            Lint::LeftRecursion {
+                rule : String::from("Sum"),
+                path : vec![String::from("Sum"), String::from("Product"), String::from("Sum")],
+            },
+            Lint::UnusedRule(String::from("Unused")),
+        ]
+    );
+    assert_eq!(
+        lints[0].to_string(),
+        "rule 'Keyword': alternative `\"int\"` can never match, as `\"in\"` always matches first"
+    );
+    assert_eq!(
+        lints[4].to_string(),
+        "rule 'Sum' is left recursive: Sum -> Product -> Sum"
+    );
+}
@@ -0,0 +1,60 @@
+// The grammar macro refers to `volt_parse::..`, which is this crate
+use crate as volt_parse;
+use crate::prelude::*;
+use volt_parse_macros::grammar;
+
+grammar! {
+    pub expr -> i64 = l:term "+" r:expr { l + r } / l:term "-" r:expr { l - r } / term ;
+    term -> i64 = l:atom '*' r:term { l * r } / atom ;
+    atom -> i64 = number / '(' e:expr ')' { e } / '-' a:atom { -a } ;
+    number -> i64 = digits:['0'..='9']+ { digits.into_iter().collect::<String>().parse().unwrap() } ;
+
+    ident -> String = first:[ 'a'..='z' | '_' ] rest:['a'..='z' | '0'..='9' | '_']* !keyword_let {
+        std::iter::once(first).chain(rest).collect()
+    } ;
+    keyword_let -> () = "let" ![ 'a'..='z' ] {} ;
+    binding -> (String, Vec<i64>) = keyword_let ' ' name:ident " = " values:@{ sep_by(number(), char_single(',')) } {
+        (name, values)
+    } ;
+    pair -> (char, String, Option<char>) = [^ ' ' | '\n'] ident ';'? ;
+    peek -> (String, char) = &ident . ;
+}
+
+#[test]
+fn grammar_macro_actions_test()
+{
+    let res = expr()(&ParserInput::new("2*(3+4)-10*-1"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       24,
+            pos :       FilePos {
+                line :   1,
+                column : 13,
+            },
+            remainder : "",
+        })
+    );
+}
+
+#[test]
+fn grammar_macro_predicates_test()
+{
+    assert_eq!(
+        binding()(&ParserInput::new("let x_1 = 4,5,6")).map(|r| r.val),
+        Ok((String::from("x_1"), vec![4, 5, 6]))
+    );
+    assert!(binding()(&ParserInput::new("letx = 4")).is_err());
+
+    assert_eq!(
+        pair()(&ParserInput::new("#abc;")).map(|r| r.val),
+        Ok(('#', String::from("abc"), Some(';')))
+    );
+    assert_eq!(
+        peek()(&ParserInput::new("ab")).map(|r| (r.val, r.remainder)),
+        Ok(((String::from("ab"), 'a'), "b"))
+    );
+}
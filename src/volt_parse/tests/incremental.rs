@@ -0,0 +1,167 @@
+use crate::prelude::*;
+
+fn ws<'a>() -> impl Parser<'a, String> { many_air() }
+
+fn ident<'a>() -> impl Parser<'a, CstNode> { node("ident", one_or_many(ascii_alpha())) }
+
+fn number<'a>() -> impl Parser<'a, CstNode> { node("number", one_or_many(ascii_digit())) }
+
+fn item<'a>() -> impl Parser<'a, CstNode>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, CstNode> { or(or(ident(), number()), list())(ind) }
+}
+
+fn list<'a>() -> impl Parser<'a, CstNode>
+{
+    node(
+        "list",
+        then(
+            char_single('('),
+            then(
+                none_or_many(then(ws(), item(), take_right)),
+                then(ws(), char_single(')'), take_right),
+                take_left,
+            ),
+            take_right,
+        ),
+    )
+}
+
+fn stmt<'a>() -> impl Parser<'a, CstNode>
+{
+    node(
+        "stmt",
+        then(
+            ident(),
+            then(
+                then(ws(), char_single('='), take_right),
+                then(
+                    then(ws(), item(), take_right),
+                    then(ws(), char_single(';'), take_right),
+                    take_left,
+                ),
+                take_right,
+            ),
+            take_left,
+        ),
+    )
+}
+
+fn doc<'a>() -> impl Parser<'a, CstNode>
+{
+    node(
+        "doc",
+        all(then(none_or_many(then(ws(), stmt(), take_right)), ws(), take_left)),
+    )
+}
+
+fn from_scratch(text : &str) -> Result<CstNode, PErr> { doc()(&ParserInput::new(text)).map(|r| r.val) }
+
+const DOC : &str = "a = 1;\nb = (x (2 3) y);\n\nc = (4 five);\n";
+
+#[test]
+fn incremental_reuses_unaffected_nodes_test()
+{
+    let mut inc = IncrementalParser::new(|ind| doc()(ind), DOC);
+    assert_eq!(inc.tree(), &from_scratch(DOC));
+
+    // Replace the `3` inside the second statement
+    let offset = DOC.find('3').unwrap();
+    let tree = inc.edit(&Edit::new(offset..offset + 1, "33 z")).unwrap().clone();
+
+    assert_eq!(inc.text(), "a = 1;\nb = (x (2 33 z) y);\n\nc = (4 five);\n");
+    assert_eq!(tree, from_scratch(inc.text()));
+    // The first and last statements are reused whole, as are `b`, `x`, `2` and `y`
+    assert_eq!(inc.reused(), 6);
+
+    let last = tree.unwrap().nodes().last().unwrap().clone();
+    assert_eq!(last.span, Span::new(FilePos::new(4, 0), FilePos::new(4, 13)));
+}
+
+// Applies random edits, checking after each that the tree is the same as parsing the text from scratch
+fn check_random_edits<F>(parser : F, text : &str, snippets : &[&str])
+where
+    F : for<'a> Fn(&ParserInput<'a>) -> POut<'a, CstNode> + Clone,
+{
+    let mut inc = IncrementalParser::new(parser.clone(), text);
+    let mut seed = 7u64;
+    let mut rand = |n : usize| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((seed >> 33) as usize) % n.max(1)
+    };
+
+    let mut reused = 0;
+    for _ in 0..300
+    {
+        let len = inc.text().len();
+        let start = rand(len + 1);
+        let end = (start + rand(4)).min(len);
+        let snippet = snippets[rand(snippets.len())];
+
+        let tree = inc.edit(&Edit::new(start..end, snippet)).unwrap().clone();
+        assert_eq!(
+            tree,
+            parser(&ParserInput::new(inc.text())).map(|r| r.val),
+            "after editing {start}..{end} into {:?}",
+            inc.text()
+        );
+        reused += inc.reused();
+    }
+    assert!(reused > 0);
+}
+
+#[test]
+fn incremental_matches_from_scratch_test()
+{
+    let snippets = ["a", "1", " ", "\n", "(", ")", ";", "=", "", "x = (1 y);\n", "(q (r))"];
+    check_random_edits(|ind| doc()(ind), DOC, &snippets);
+}
+
+// A node nested directly within another of the same kind, both starting at the same offset
+fn sum<'a>() -> impl Parser<'a, CstNode>
+{
+    let digits = || one_or_many(ascii_digit());
+    node(
+        "e",
+        then(
+            node("e", digits()),
+            maybe(then(char_single('+'), digits(), take_right)),
+            take_left,
+        ),
+    )
+}
+
+fn sums<'a>() -> impl Parser<'a, CstNode> { node("doc", all(none_or_many(then(sum(), char_single(';'), take_left)))) }
+
+#[test]
+fn incremental_nested_same_kind_test()
+{
+    let mut inc = IncrementalParser::new(|ind| sums()(ind), "1+2;3;");
+    let tree = inc.edit(&Edit::new(5..5, "4")).unwrap().clone();
+
+    println!("{:#?}", tree);
+
+    assert_eq!(inc.text(), "1+2;34;");
+    assert_eq!(tree, sums()(&ParserInput::new("1+2;34;")).map(|r| r.val));
+    assert!(tree.is_ok());
+
+    check_random_edits(|ind| sums()(ind), "1+2;3;45+6;7+8;", &["1", "+", ";", "", "2+3;"]);
+}
+
+#[test]
+fn incremental_invalid_edit_test()
+{
+    let mut inc = IncrementalParser::new(|ind| doc()(ind), "a = \u{e9};\n");
+    let before = inc.tree().clone();
+
+    let (start, end) = (3, 1);
+    assert_eq!(
+        inc.edit(&Edit::new(start..end, "x")),
+        Err(EditError::Reversed(start..end))
+    );
+    assert_eq!(inc.edit(&Edit::new(2..40, "x")), Err(EditError::OutOfBounds(40)));
+    assert_eq!(inc.edit(&Edit::new(5..6, "x")), Err(EditError::NotCharBoundary(5)));
+
+    assert_eq!(inc.text(), "a = \u{e9};\n");
+    assert_eq!(inc.tree(), &before);
+}
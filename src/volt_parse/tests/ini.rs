@@ -0,0 +1,98 @@
+use crate::prelude::*;
+use crate::volt_parse::formats::ini::*;
+
+const CONFIG : &str = "; service settings
+name = volt   ; inline comment
+url: http://host/a#b
+
+[database]
+host = \"db.local\\tprimary\"
+path = 'C:\\data\\'  # literal
+retries =
+long key = first \\
+    second
+
+[ cache ]
+size=64
+[database]
+timeout = 30
+";
+
+#[test]
+fn ini_test()
+{
+    let res = parse_ini(CONFIG, IniFormat::ini());
+
+    println!("{:#?}", res);
+
+    let ini = res.unwrap();
+    assert_eq!(ini.get("", "name"), Some("volt"));
+    assert_eq!(ini.get("", "url"), Some("http://host/a#b"));
+    assert_eq!(ini.get("database", "host"), Some("db.local\tprimary"));
+    assert_eq!(ini.get("database", "path"), Some("C:\\data\\"));
+    assert_eq!(ini.get("database", "retries"), Some(""));
+    assert_eq!(ini.get("database", "long key"), Some("first second"));
+    assert_eq!(ini.get("database", "timeout"), Some("30"));
+    assert_eq!(ini.get("cache", "size"), Some("64"));
+    assert_eq!(ini.get("cache", "name"), None);
+    assert_eq!(
+        ini.sections
+            .iter()
+            .map(|s| s.name.as_ref().map(|n| n.val.as_str()))
+            .collect::<Vec<_>>(),
+        vec![None, Some("database"), Some("cache")]
+    );
+
+    let long = ini.section("database").unwrap().entry("long key").unwrap();
+    assert_eq!(
+        long.key,
+        Spanned {
+            val :  String::from("long key"),
+            span : Span::new(FilePos::new(9, 0), FilePos::new(9, 8)),
+        }
+    );
+    assert_eq!(long.value.span, Span::new(FilePos::new(9, 11), FilePos::new(10, 10)));
+    assert_eq!(
+        ini.section("cache").unwrap().name.as_ref().unwrap().span,
+        Span::new(FilePos::new(12, 2), FilePos::new(12, 7))
+    );
+}
+
+#[test]
+fn dotenv_test()
+{
+    let text = "# env\nexport HOME=/home/volt\nDATA=\"${HOME}/data\"\nRAW='${HOME}'\nLOG=${DATA}/log # where\n";
+    let env = parse_ini(text, IniFormat::dotenv()).unwrap();
+
+    println!("{:#?}", env);
+
+    assert_eq!(env.get("", "HOME"), Some("/home/volt"));
+    assert_eq!(env.get("", "DATA"), Some("/home/volt/data"));
+    assert_eq!(env.get("", "RAW"), Some("${HOME}"));
+    assert_eq!(env.get("", "LOG"), Some("/home/volt/data/log"));
+
+    // `$${` is a literal `${`, while any other `$` is kept as it is
+    let escaped = parse_ini("A=1\nB=\"$${A} is ${A}\"\nC=$$A $${\n", IniFormat::dotenv()).unwrap();
+    assert_eq!(escaped.get("", "B"), Some("${A} is 1"));
+    assert_eq!(escaped.get("", "C"), Some("$$A ${"));
+
+    // Sections are not allowed within a .env file
+    assert!(parse_ini("[a]\nb=c", IniFormat::dotenv()).is_err());
+}
+
+#[test]
+fn ini_error_test()
+{
+    let err_at = |text, format| parse_ini(text, format).map_err(|e| (e.pos.line, e.pos.column));
+
+    assert_eq!(err_at("a = 1\nb 2\n", IniFormat::ini()), Err((2, 3)));
+    assert_eq!(err_at("[a\nb = 1", IniFormat::ini()), Err((1, 2)));
+    assert_eq!(err_at("a = \"open\n", IniFormat::ini()), Err((1, 4)));
+    assert_eq!(err_at("a = \"x\" y", IniFormat::ini()), Err((1, 8)));
+    // Interpolation only sees earlier keys
+    assert_eq!(err_at("A=${B}\nB=1\n", IniFormat::dotenv()), Err((1, 2)));
+    assert_eq!(
+        parse_ini("A=${B}", IniFormat::ini()).map(|i| i.get("", "A").map(String::from)),
+        Ok(Some(String::from("${B}")))
+    );
+}
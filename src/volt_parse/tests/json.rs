@@ -0,0 +1,94 @@
+use crate::prelude::*;
+use crate::volt_parse::{formats::json::*, tests::fixtures};
+
+#[test]
+fn json_fixtures_test()
+{
+    let mut failed = vec![];
+    // The JSONTestSuite cases, as bytes since some of them are not UTF-8
+    for (name, bytes) in fixtures("json", "json")
+    {
+        // Text that is not UTF-8 is rejected before it reaches the parser
+        let res = std::str::from_utf8(&bytes).map(parse_json);
+        let ok = match &name[..2]
+        {
+            "y_" => matches!(res, Ok(Ok(_))),
+            "n_" => !matches!(res, Ok(Ok(_))),
+            // Either way is allowed, so long as the parser does not panic
+            _ => true,
+        };
+        if !ok
+        {
+            failed.push(format!("{name}: {res:?}"));
+        }
+    }
+
+    println!("{:#?}", failed);
+
+    assert!(failed.is_empty());
+}
+
+#[test]
+fn json_value_test()
+{
+    let res = parse_json("{\"name\": \"volt\", \"tags\": [1, -2.5e1, true, null]}\n");
+
+    println!("{:#?}", res);
+
+    let doc = res.unwrap();
+    assert_eq!(doc.val.get("name").and_then(|v| v.as_str()), Some("volt"));
+    let tags = doc.val.get("tags").unwrap();
+    assert_eq!(tags.at(1).and_then(|v| v.as_f64()), Some(-25.0));
+    assert_eq!(tags.at(2).and_then(|v| v.as_bool()), Some(true));
+    assert!(tags.at(3).unwrap().is_null());
+    assert_eq!(tags.at(4), None);
+    assert_eq!(doc.span, Span::new(FilePos::new(1, 0), FilePos::new(1, 49)));
+
+    let JsonValue::Object(members) = &doc.val
+    else
+    {
+        panic!("expected an object")
+    };
+    assert_eq!(
+        members[1].0,
+        Spanned {
+            val :  String::from("tags"),
+            span : Span::new(FilePos::new(1, 17), FilePos::new(1, 23)),
+        }
+    );
+    assert_eq!(members[1].1.span, Span::new(FilePos::new(1, 25), FilePos::new(1, 48)));
+    // The last of any duplicate names is taken
+    assert_eq!(
+        parse_json(r#"{"a": 1, "a": 2}"#)
+            .unwrap()
+            .val
+            .get("a")
+            .and_then(|v| v.as_f64()),
+        Some(2.0)
+    );
+}
+
+#[test]
+fn json_error_test()
+{
+    let err_at = |text| parse_json(text).map_err(|e| (e.pos.line, e.pos.column));
+
+    assert_eq!(err_at("{\n  \"a\": [1, 2,]\n}"), Err((2, 13)));
+    assert_eq!(err_at("{\n  \"a\": \"b\\q\"\n}"), Err((2, 9)));
+    assert_eq!(err_at("[1, 2] x"), Err((1, 7)));
+    assert_eq!(err_at("[01]"), Err((1, 2)));
+    assert_eq!(err_at("{\"a\" 1}"), Err((1, 5)));
+    assert_eq!(err_at(""), Err((1, 0)));
+
+    let deep = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(parse_json(&deep(JSON_MAX_DEPTH + 1)).is_ok());
+    assert_eq!(err_at(&deep(JSON_MAX_DEPTH + 2)), Err((1, JSON_MAX_DEPTH + 1)));
+}
+
+#[test]
+fn json_large_array_test()
+{
+    // Arrays are repetitions, which must not use up the stack however long they are
+    let text = format!("[{}0]", "0,".repeat(100_000));
+    assert!(parse_json(&text).is_ok());
+}
@@ -0,0 +1,188 @@
+use crate::prelude::*;
+
+#[test]
+fn keyword_ci_test()
+{
+    let res = then(keyword_ci("select"), keyword_ci(" * FROM"), left_right)(&ParserInput::new("SeLeCt * from users"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       (String::from("SeLeCt"), String::from(" * from")),
+            pos :       FilePos {
+                line :   1,
+                column : 13,
+            },
+            remainder : " users",
+        })
+    );
+
+    // Only ASCII letters are folded, and a multibyte character is never split
+    assert!(keyword_ci("é")(&ParserInput::new("É")).is_err());
+    assert!(keyword_ci("ab")(&ParserInput::new("aé")).is_err());
+    assert!(keyword_ci("select")(&ParserInput::new("sel")).is_err());
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn keyword_fold_test()
+{
+    let res = then(keyword_fold("straße"), keyword_fold("\n"), left_right)(&ParserInput::new("STRASSE\n = ÉTÉ"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       (String::from("STRASSE"), String::from("\n")),
+            pos :       FilePos {
+                line : 2, column : 0
+            },
+            remainder : " = ÉTÉ",
+        })
+    );
+
+    let res = keyword_fold("été")(&ParserInput::new("ÉTÉ = summer"));
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       String::from("ÉTÉ"),
+            pos :       FilePos {
+                line : 1, column : 3
+            },
+            remainder : " = summer",
+        })
+    );
+
+    // Without normalization a decomposed accent is a different character
+    assert!(keyword_fold("été")(&ParserInput::new("E\u{301}te\u{301}")).is_err());
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn keyword_normalized_test()
+{
+    // "café" written with a combining acute accent
+    let decomposed = "cafe\u{301} au lait";
+
+    let res = keyword_normalized("café", Normalization::Nfc)(&ParserInput::new(decomposed));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       String::from("cafe\u{301}"),
+            pos :       FilePos {
+                line : 1, column : 5
+            },
+            remainder : " au lait",
+        })
+    );
+
+    // A match may not stop before a combining mark
+    assert!(keyword_normalized("cafe", Normalization::Nfc)(&ParserInput::new(decomposed)).is_err());
+    assert!(keyword("cafe")(&ParserInput::new(decomposed)).is_ok());
+
+    // Compatibility normalization also matches ligatures and full width forms
+    assert!(keyword_normalized("file", Normalization::Nfc)(&ParserInput::new("ﬁle")).is_err());
+    assert_eq!(
+        keyword_normalized("file", Normalization::Nfkc)(&ParserInput::new("ﬁle")).map(|r| r.val),
+        Ok(String::from("ﬁle"))
+    );
+    assert_eq!(
+        keyword_normalized("ABC", Normalization::Nfkc)(&ParserInput::new("ＡＢＣ!")).map(|r| (r.val, r.remainder)),
+        Ok((String::from("ＡＢＣ"), "!"))
+    );
+
+    let res = keyword_fold_normalized("CAFÉ", Normalization::Nfc)(&ParserInput::new(decomposed));
+    assert_eq!(res.map(|r| r.val), Ok(String::from("cafe\u{301}")));
+    let res = keyword_fold_normalized("FILE", Normalization::Nfkc)(&ParserInput::new("ﬁle"));
+    assert_eq!(res.map(|r| r.val), Ok(String::from("ﬁle")));
+}
+
+#[test]
+fn one_of_keywords_test()
+{
+    let ops = one_of_keywords(&["=", "==", "!", "!=", "<", "<=", "<<", "<<="]);
+    let res = one_or_many(ops)(&ParserInput::new("<<====!!=x"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       vec![
+                String::from("<<="),
+                String::from("=="),
+                String::from("="),
+                String::from("!"),
+                String::from("!="),
+            ],
+            pos :       FilePos {
+                line : 1, column : 9
+            },
+            remainder : "x",
+        })
+    );
+
+    // A keyword that is a prefix of one that only partially matches is still found
+    let res = one_of_keywords(&["for", "foreach", "\n"])(&ParserInput::new("foreac"));
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       String::from("for"),
+            pos :       FilePos {
+                line : 1, column : 3
+            },
+            remainder : "eac",
+        })
+    );
+    assert_eq!(
+        one_of_keywords(&["for", "\n"])(&ParserInput::new("\nfor")).map(|r| r.pos),
+        Ok(FilePos {
+            line : 2, column : 0
+        })
+    );
+    assert_eq!(
+        one_of_keywords(&["for", "while"])(&ParserInput::new("fo")),
+        Err(PErr {
+            pos : FilePos {
+                line : 1, column : 0
+            },
+        })
+    );
+}
+
+#[test]
+fn one_of_keywords_val_test()
+{
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Op
+    {
+        Assign,
+        Eq,
+        Arrow,
+        Minus,
+    }
+
+    let res = sep_by(
+        one_of_keywords_val(&[("=", Op::Assign), ("-", Op::Minus), ("==", Op::Eq), ("->", Op::Arrow)]),
+        char_single(' '),
+    )(&ParserInput::new("== - -> = ->"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res.map(|r| r.val),
+        Ok(vec![Op::Eq, Op::Minus, Op::Arrow, Op::Assign, Op::Arrow])
+    );
+
+    // Later keywords replace the values of earlier duplicates
+    let trie = [("é", 1), ("éa", 2), ("é", 3)].into_iter().collect::<KeywordTrie<_>>();
+    assert_eq!(trie.longest_match("éb"), Some((2, &3)));
+    assert_eq!(trie.longest_match("éa"), Some((3, &2)));
+    assert_eq!(trie.longest_match("e"), None);
+}
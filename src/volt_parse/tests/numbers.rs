@@ -0,0 +1,153 @@
+use crate::prelude::*;
+
+#[test]
+fn integer_test()
+{
+    let res = sep_by(integer::<i32>(), char_single(','))(&ParserInput::new("12,-7,+3,0042x"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       vec![12, -7, 3, 42],
+            pos :       FilePos {
+                line :   1,
+                column : 13,
+            },
+            remainder : "x",
+        })
+    );
+
+    // Unsigned integers never read a minus sign
+    assert!(integer::<u8>()(&ParserInput::new("-1")).is_err());
+    assert!(integer::<i8>()(&ParserInput::new("-")).is_err());
+    assert_eq!(
+        integer::<i128>()(&ParserInput::new("-170141183460469231731687303715884105728")).map(|r| r.val),
+        Ok(i128::MIN)
+    );
+    assert_eq!(
+        integer::<u64>()(&ParserInput::new("18446744073709551615")).map(|r| r.val),
+        Ok(u64::MAX)
+    );
+}
+
+#[test]
+fn integer_overflow_test()
+{
+    assert_eq!(integer::<u8>()(&ParserInput::new("255")).map(|r| r.val), Ok(255));
+    assert_eq!(
+        integer::<u8>()(&ParserInput::new("256")),
+        Err(PErr {
+            pos : FilePos {
+                line : 1, column : 0
+            },
+        })
+    );
+
+    let checked = integer_checked::<i8>(IntFormat::default());
+    let res = checked(&ParserInput::new("-129;"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       Err(IntError::TooSmall),
+            pos :       FilePos {
+                line : 1, column : 4
+            },
+            remainder : ";",
+        })
+    );
+    assert_eq!(
+        checked(&ParserInput::new("128")).map(|r| r.val),
+        Ok(Err(IntError::TooLarge))
+    );
+    assert_eq!(checked(&ParserInput::new("-128")).map(|r| r.val), Ok(Ok(-128)));
+}
+
+#[test]
+fn integer_radix_and_separator_test()
+{
+    let p = integer_with::<i64>(IntFormat::default().with_prefixes().with_separator('_'));
+    let parse = |text| p(&ParserInput::new(text)).map(|r| (r.val, r.remainder));
+
+    assert_eq!(parse("0xFF_ff"), Ok((0xffff, "")));
+    assert_eq!(parse("-0o17"), Ok((-0o17, "")));
+    assert_eq!(parse("0b1010_0101"), Ok((0b1010_0101, "")));
+    assert_eq!(parse("1_000_000"), Ok((1_000_000, "")));
+    // Separators must be between digits, and a prefix must be followed by a digit
+    assert_eq!(parse("1__0"), Ok((1, "__0")));
+    assert_eq!(parse("1_"), Ok((1, "_")));
+    assert_eq!(parse("0b2"), Ok((0, "b2")));
+    assert_eq!(parse("0x"), Ok((0, "x")));
+    assert!(parse("_1").is_err());
+
+    // Without prefixes the `x` is not part of the number
+    assert_eq!(
+        integer::<u32>()(&ParserInput::new("0x10")).map(|r| r.remainder),
+        Ok("x10")
+    );
+    assert!(integer_with::<u8>(IntFormat::default().with_prefixes())(&ParserInput::new("0x100")).is_err());
+}
+
+#[test]
+fn float_test()
+{
+    let res = sep_by(float::<f64>(), char_single(' '))(&ParserInput::new("1 -2.5 3e2 4.25E-2 +0.1 7e x"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       vec![
+                Float(1.0),
+                Float(-2.5),
+                Float(300.0),
+                Float(0.0425),
+                Float(0.1),
+                Float(7.0)
+            ],
+            pos :       FilePos {
+                line :   1,
+                column : 25,
+            },
+            remainder : "e x",
+        })
+    );
+
+    // A dot must be followed by digits to be part of the number
+    assert_eq!(
+        float::<f64>()(&ParserInput::new("1..2")).map(|r| (r.val, r.remainder)),
+        Ok((Float(1.0), "..2"))
+    );
+    assert!(float::<f64>()(&ParserInput::new(".5")).is_err());
+    assert!(float::<f64>()(&ParserInput::new("inf")).is_err());
+
+    // Values are rounded exactly, as Rust itself would
+    assert_eq!(
+        float::<f32>()(&ParserInput::new("16777217")).map(|r| r.val),
+        Ok(Float(16777216.0))
+    );
+    assert_eq!(
+        float::<f64>()(&ParserInput::new("1e400")).map(|r| r.val),
+        Ok(Float(f64::INFINITY))
+    );
+    assert_ne!(Float(0.0f64), Float(-0.0));
+}
+
+#[test]
+fn float_format_test()
+{
+    let p = float_with::<f64>(FloatFormat::default().with_special().with_separator('_'));
+    let parse = |text| p(&ParserInput::new(text)).map(|r| (r.val, r.remainder));
+
+    assert_eq!(parse("1_000.000_5e1_0"), Ok((Float(1000.0005e10), "")));
+    assert_eq!(parse("-Infinity!"), Ok((Float(f64::NEG_INFINITY), "!")));
+    assert_eq!(parse("INF"), Ok((Float(f64::INFINITY), "")));
+    assert_eq!(parse("nan").map(|(f, _)| f.0.is_nan()), Ok(true));
+    assert_eq!(parse("-nan").map(|(f, _)| f.0.is_sign_negative()), Ok(true));
+    assert!(parse("na").is_err());
+}
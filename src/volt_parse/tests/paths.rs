@@ -0,0 +1,362 @@
+use crate::prelude::*;
+use crate::volt_parse::paths::*;
+
+#[test]
+fn test_path_parse_relative()
+{
+    let res = path_parser()(&ParserInput::new("my/normal/relative/path.txt"));
+
+    println!("{:?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       UnsafePath {
+                pieces :    vec![
+                    PathPiece::Name(String::from("my")),
+                    PathPiece::Name(String::from("normal")),
+                    PathPiece::Name(String::from("relative")),
+                    PathPiece::Name(String::from("path.txt"))
+                ],
+                path_type : UnsafePathType::Relative,
+                prefix :    None,
+                dialect :   PathDialect::Posix,
+                verbatim :  false,
+            },
+            pos :       FilePos {
+                line :   1,
+                column : 27,
+            },
+            remainder : "",
+        })
+    );
+}
+
+#[test]
+fn test_path_parse_absolute()
+{
+    let res = path_parser()(&ParserInput::new("/home/username/etc.txt"));
+
+    println!("{:?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       UnsafePath {
+                pieces :    vec![
+                    PathPiece::Name(String::from("home")),
+                    PathPiece::Name(String::from("username")),
+                    PathPiece::Name(String::from("etc.txt"))
+                ],
+                path_type : UnsafePathType::Absolute,
+                prefix :    None,
+                dialect :   PathDialect::Posix,
+                verbatim :  false,
+            },
+            pos :       FilePos {
+                line :   1,
+                column : 22,
+            },
+            remainder : "",
+        })
+    );
+}
+
+#[test]
+fn test_path_parse_home_full()
+{
+    let res = path_parser()(&ParserInput::new("~/.config/service.toml"));
+
+    println!("{:?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       UnsafePath {
+                pieces :    vec![
+                    PathPiece::Name(String::from(".config")),
+                    PathPiece::Name(String::from("service.toml"))
+                ],
+                path_type : UnsafePathType::Home,
+                prefix :    None,
+                dialect :   PathDialect::Posix,
+                verbatim :  false,
+            },
+            pos :       FilePos {
+                line :   1,
+                column : 22,
+            },
+            remainder : "",
+        })
+    );
+}
+
+#[test]
+fn test_path_parse_home_only()
+{
+    let res1 = path_parser()(&ParserInput::new("~"));
+
+    println!("{:?}", res1);
+
+    assert_eq!(
+        res1,
+        Ok(PRes {
+            val :       UnsafePath {
+                pieces :    vec![],
+                path_type : UnsafePathType::Home,
+                prefix :    None,
+                dialect :   PathDialect::Posix,
+                verbatim :  false,
+            },
+            pos :       FilePos {
+                line : 1, column : 1
+            },
+            remainder : "",
+        })
+    );
+}
+
+#[test]
+fn test_path_parse_up()
+{
+    let res = path_parser()(&ParserInput::new("~/.."));
+
+    println!("{:?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       UnsafePath {
+                pieces :    vec![PathPiece::Up],
+                path_type : UnsafePathType::Home,
+                prefix :    None,
+                dialect :   PathDialect::Posix,
+                verbatim :  false,
+            },
+            pos :       FilePos {
+                line : 1, column : 4
+            },
+            remainder : "",
+        })
+    );
+}
+
+#[test]
+fn test_path_parse_route()
+{
+    let res = path_parser()(&ParserInput::new("~/../././//test2./..//username/etc.txt"));
+
+    println!("{:?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       UnsafePath {
+                pieces :    vec![
+                    PathPiece::Up,
+                    PathPiece::Name(String::from("test2.")),
+                    PathPiece::Up,
+                    PathPiece::Name(String::from("username")),
+                    PathPiece::Name(String::from("etc.txt"))
+                ],
+                path_type : UnsafePathType::Home,
+                prefix :    None,
+                dialect :   PathDialect::Posix,
+                verbatim :  false,
+            },
+            pos :       FilePos {
+                line :   1,
+                column : 38,
+            },
+            remainder : "",
+        })
+    );
+}
+
+#[test]
+fn test_path_forbid_name_with_double_dots()
+{
+    let res = path_parser()(&ParserInput::new("an/inva..lid/name.txt"));
+
+    println!("{:?}", res);
+
+    assert_eq!(
+        res,
+        Err(PErr {
+            pos : FilePos {
+                line : 1, column : 0
+            },
+        })
+    );
+}
+
+#[test]
+fn test_path_forbid_name_with_double_dots_2()
+{
+    let res = path_parser()(&ParserInput::new("..invalid_2"));
+
+    println!("{:?}", res);
+
+    assert_eq!(
+        res,
+        Err(PErr {
+            pos : FilePos {
+                line : 1, column : 0
+            },
+        })
+    );
+}
+
+#[test]
+fn test_path_parse_windows()
+{
+    let parse = |text : &str| path_parser_with(PathDialect::Windows)(&ParserInput::new(text)).map(|r| r.val);
+
+    let res = parse(r"c:\Users/me\..\file.txt");
+
+    println!("{:?}", res);
+
+    assert_eq!(
+        res,
+        Ok(UnsafePath {
+            pieces :    vec![
+                PathPiece::Name(String::from("Users")),
+                PathPiece::Name(String::from("me")),
+                PathPiece::Up,
+                PathPiece::Name(String::from("file.txt"))
+            ],
+            path_type : UnsafePathType::Absolute,
+            prefix :    Some(PathPrefix::Drive('C')),
+            dialect :   PathDialect::Windows,
+            verbatim :  false,
+        })
+    );
+
+    // A drive without a delimiter is relative to the current directory of that drive
+    let drive_relative = parse("D:notes.txt").unwrap();
+    assert_eq!(drive_relative.path_type, UnsafePathType::Relative);
+    assert_eq!(drive_relative.prefix, Some(PathPrefix::Drive('D')));
+
+    let unc = parse(r"\\server\share\dir").unwrap();
+    assert_eq!(unc.path_type, UnsafePathType::Absolute);
+    assert_eq!(
+        unc.prefix,
+        Some(PathPrefix::Unc {
+            server : String::from("server"),
+            share :  String::from("share"),
+        })
+    );
+    assert_eq!(unc.pieces, vec![PathPiece::Name(String::from("dir"))]);
+
+    // Verbatim paths keep `.` and `..` as names and only split on `\`
+    let verbatim = parse(r"\\?\C:\a/b\..").unwrap();
+    assert!(verbatim.verbatim);
+    assert_eq!(
+        verbatim.pieces,
+        vec![
+            PathPiece::Name(String::from("a/b")),
+            PathPiece::Name(String::from(".."))
+        ]
+    );
+    let verbatim_unc = parse(r"\\?\UNC\server\share\x").unwrap();
+    assert_eq!(
+        verbatim_unc,
+        UnsafePath {
+            prefix : unc.prefix.clone(),
+            pieces : vec![PathPiece::Name(String::from("x"))],
+            verbatim : true,
+            ..unc.clone()
+        }
+    );
+
+    // Names may not contain the characters Windows reserves
+    assert!(parse("what?.txt").is_err());
+    assert!(parse(r"\\server").is_err());
+    assert!(parse("").is_err());
+}
+
+#[test]
+fn test_path_normalize()
+{
+    let base = SafePath {
+        prefix : None,
+        pieces : vec![String::from("srv"), String::from("app")],
+    };
+    let home = SafePath {
+        prefix : None,
+        pieces : vec![String::from("home"), String::from("me")],
+    };
+    let normalize = |text : &str| {
+        path_parser()(&ParserInput::new(text))
+            .unwrap()
+            .val
+            .normalize(&base, &home)
+    };
+
+    assert_eq!(
+        normalize("./logs/../data/./x.db").unwrap().to_string(),
+        "/srv/app/data/x.db"
+    );
+    assert_eq!(normalize("../../etc").unwrap().to_string(), "/etc");
+    assert_eq!(normalize("/var/../tmp").unwrap().to_string(), "/tmp");
+    assert_eq!(normalize("~/.config").unwrap().to_string(), "/home/me/.config");
+    // The third `..` would go above the root
+    assert_eq!(normalize("../../../etc"), Err(PathError::EscapesRoot(2)));
+    assert_eq!(normalize("/.."), Err(PathError::EscapesRoot(0)));
+
+    let windows_base = SafePath {
+        prefix : Some(PathPrefix::Drive('C')),
+        pieces : vec![String::from("work")],
+    };
+    let normalize = |text : &str| {
+        path_parser_with(PathDialect::Windows)(&ParserInput::new(text))
+            .unwrap()
+            .val
+            .normalize(&windows_base, &home)
+    };
+    assert_eq!(normalize(r"a\..\b").unwrap().to_string(), r"C:\work\b");
+    assert_eq!(normalize(r"\tmp").unwrap().to_string(), r"C:\tmp");
+    assert_eq!(normalize("c:x").unwrap().to_string(), r"C:\work\x");
+    assert_eq!(normalize("D:x").unwrap().to_string(), r"D:\x");
+    assert_eq!(normalize(r"\\srv\pub\a\..\doc").unwrap().to_string(), r"\\srv\pub\doc");
+    assert_eq!(normalize(r"\\srv\pub\.."), Err(PathError::EscapesRoot(0)));
+    // Verbatim `..` is a name, so it never goes up
+    assert_eq!(normalize(r"\\?\C:\..").unwrap().pieces, vec![String::from("..")]);
+}
+
+#[test]
+fn test_path_display_round_trip()
+{
+    for (dialect, text) in [
+        (PathDialect::Posix, "./a/../b"),
+        (PathDialect::Posix, "/"),
+        (PathDialect::Posix, "~/x/y.txt"),
+        (PathDialect::Windows, r".\a\..\b"),
+        (PathDialect::Windows, r"C:\Windows\System32"),
+        (PathDialect::Windows, "C:relative"),
+        (PathDialect::Windows, r"\\server\share\"),
+        (PathDialect::Windows, r"\\?\C:\..\x"),
+        (PathDialect::Windows, r"\\?\UNC\server\share\x"),
+        (PathDialect::Windows, r"\\?\Volume\x"),
+    ]
+    {
+        let path = path_parser_with(dialect)(&ParserInput::new(text)).unwrap().val;
+        let shown = path.to_string();
+
+        println!("{text} -> {shown}");
+
+        assert_eq!(shown, text);
+        assert_eq!(path_parser_with(dialect)(&ParserInput::new(&shown)).unwrap().val, path);
+    }
+
+    let safe = SafePath {
+        prefix : Some(PathPrefix::Unc {
+            server : String::from("srv"),
+            share :  String::from("pub"),
+        }),
+        pieces : vec![String::from("doc")],
+    };
+    let shown = safe.to_string();
+    let reparsed = path_parser_with(safe.dialect())(&ParserInput::new(&shown)).unwrap().val;
+    assert_eq!(reparsed.normalize(&SafePath::root(), &SafePath::root()), Ok(safe));
+}
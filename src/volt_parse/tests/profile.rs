@@ -0,0 +1,46 @@
+use crate::prelude::*;
+
+fn items<'a>() -> impl Parser<'a, Vec<Vec<char>>>
+{
+    one_or_many(profile(
+        "item",
+        or(
+            profile("number", one_or_many(ascii_digit())),
+            profile("word", one_or_many(ascii_alpha())),
+        ),
+    ))
+}
+
+#[test]
+fn profile_counts_test()
+{
+    let (res, prof) = with_profile(|| items()(&ParserInput::new("ab12cd")));
+
+    println!("{}", prof);
+
+    assert_eq!(res.map(|r| r.val.len()), Ok(3));
+
+    let counts = |name : &str| {
+        let stats = &prof.rules[name];
+        (stats.calls, stats.successes, stats.backtracks, stats.bytes)
+    };
+    assert_eq!(counts("item"), (4, 3, 1, 6));
+    assert_eq!(counts("number"), (4, 1, 3, 2));
+    assert_eq!(counts("word"), (3, 2, 1, 4));
+    assert_eq!(prof.rules["word"].success_rate(), 2.0 / 3.0);
+
+    // The outermost rule contains the others, so it takes the longest
+    assert_eq!(prof.sorted()[0].0, "item");
+    let item = &prof.rules["item"];
+    assert!(item.self_time <= item.time);
+    assert!(prof.to_string().lines().nth(1).unwrap().starts_with("item "));
+}
+
+#[test]
+fn profile_inactive_test()
+{
+    assert!(items()(&ParserInput::new("ab")).is_ok());
+
+    let (_, prof) = with_profile(|| ());
+    assert!(prof.rules.is_empty());
+}
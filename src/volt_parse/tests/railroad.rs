@@ -0,0 +1,69 @@
+use crate::prelude::*;
+use crate::volt_parse::paths::{path_grammar, path_parser};
+
+// Compares against the snapshot, or rewrites it when `UPDATE_SNAPSHOTS` is set
+fn assert_snapshot(name : &str, actual : &str)
+{
+    let path = format!("{}/src/volt_parse/tests/snapshots/{name}", env!("CARGO_MANIFEST_DIR"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some()
+    {
+        std::fs::write(&path, actual).unwrap();
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_default();
+    assert_eq!(
+        actual, expected,
+        "snapshot {name} differs, rerun with UPDATE_SNAPSHOTS=1 to accept the changes"
+    );
+}
+
+#[test]
+fn railroad_path_grammar_matches_parser_test()
+{
+    let grammar = path_grammar();
+    for path in [
+        "my/normal/relative/path.txt",
+        "/home/username/etc.txt",
+        "~/.config/service.toml",
+        "~",
+        "~/..",
+        "~/../././//test2./..//username/etc.txt",
+        "an/inva..lid/name.txt",
+        "..invalid_2",
+        ".",
+        "/",
+        "my-dir/file (1).tar.gz",
+        "tab\there",
+    ]
+    {
+        assert_eq!(
+            grammar.parse(path).is_ok(),
+            path_parser()(&ParserInput::new(path)).is_ok(),
+            "{path}"
+        );
+    }
+}
+
+#[test]
+fn railroad_svg_snapshot_test()
+{
+    let grammar = path_grammar();
+    let svg = grammar.rule("path").unwrap().to_svg();
+
+    println!("{svg}");
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert_snapshot("path.svg", &svg);
+    assert_snapshot("name.svg", &grammar.rule("name").unwrap().to_svg());
+    assert_snapshot("local_path.svg", &grammar.rule("local_path").unwrap().to_svg());
+}
+
+#[test]
+fn railroad_html_snapshot_test()
+{
+    let html = path_grammar().to_html();
+
+    assert_eq!(html.matches("<svg ").count(), 11);
+    assert!(html.contains("<a href=\"#rule-local_path\">"));
+    assert!(html.contains("<h2 id=\"rule-local_path\">local_path</h2>"));
+    assert_snapshot("path_grammar.html", &html);
+}
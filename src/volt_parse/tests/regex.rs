@@ -0,0 +1,76 @@
+use crate::prelude::*;
+
+const FLOAT : &str = r"[+-]?(\d+)(?:\.(\d+))?(?:[eE]([+-]?\d+))?";
+
+#[test]
+fn regex_test()
+{
+    let res = then(regex(FLOAT), keyword(" m"), take_left)(&ParserInput::new("-12.5e+3 m/s"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       RegexMatch {
+                text :   String::from("-12.5e+3"),
+                groups : vec![
+                    Some(String::from("-12.5e+3")),
+                    Some(String::from("12")),
+                    Some(String::from("5")),
+                    Some(String::from("+3")),
+                ],
+                names :  vec![None; 4],
+            },
+            pos :       FilePos {
+                line :   1,
+                column : 10,
+            },
+            remainder : "/s",
+        })
+    );
+
+    let res = regex(FLOAT)(&ParserInput::new("7 apples")).unwrap();
+    assert_eq!(
+        (res.val.group(1), res.val.group(2), res.val.group(9)),
+        (Some("7"), None, None)
+    );
+
+    // Matches are anchored to the current position rather than searched for
+    assert_eq!(
+        regex(FLOAT)(&ParserInput::new("x1")),
+        Err(PErr {
+            pos : FilePos {
+                line : 1, column : 0
+            },
+        })
+    );
+}
+
+#[test]
+fn regex_named_groups_and_newlines_test()
+{
+    let uuid = regex(r"(?<time>[0-9a-f]{8})-[0-9a-f]{4}-(?<version>[1-8])[0-9a-f]{3}-[0-9a-f]{4}-[0-9a-f]{12}");
+    let res = uuid(&ParserInput::new("123e4567-e89b-42d3-a456-426614174000}")).unwrap();
+    assert_eq!(res.val.name("time"), Some("123e4567"));
+    assert_eq!(res.val.name("version"), Some("4"));
+    assert_eq!(res.val.name("missing"), None);
+    assert_eq!(res.remainder, "}");
+
+    let res = then(keyword("a"), regex_str(r"(?s)/\*.*?\*/"), take_right)(&ParserInput::new("a/* one\ntwö\n */b"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       String::from("/* one\ntwö\n */"),
+            pos :       FilePos {
+                line : 3, column : 3
+            },
+            remainder : "b",
+        })
+    );
+
+    assert!(try_regex("(unclosed").is_err());
+}
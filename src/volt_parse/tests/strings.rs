@@ -0,0 +1,116 @@
+use crate::prelude::*;
+use std::borrow::Cow;
+
+#[test]
+fn string_literal_borrowed_test()
+{
+    let res = string_literal(StringFormat::double())(&ParserInput::new("\"plain text\" rest"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       Cow::Borrowed("plain text"),
+            pos :       FilePos {
+                line :   1,
+                column : 12,
+            },
+            remainder : " rest",
+        })
+    );
+    assert!(matches!(res.unwrap().val, Cow::Borrowed(_)));
+}
+
+#[test]
+fn string_literal_escapes_test()
+{
+    let res = string_literal(StringFormat::double())(&ParserInput::new(
+        r#""tab\there\n\"q\" \\ \x41\u{1F600}\u00e9\ud83d\ude00\0'"!"#,
+    ));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       Cow::Owned(String::from("tab\there\n\"q\" \\ A😀é😀\0'")),
+            pos :       FilePos {
+                line :   1,
+                column : 56,
+            },
+            remainder : "!",
+        })
+    );
+
+    let res = string_literal(StringFormat::single())(&ParserInput::new(r#"'it\'s "fine"'"#));
+    assert_eq!(res.map(|r| r.val), Ok(Cow::Owned(String::from("it's \"fine\""))));
+    assert_eq!(
+        normal_string()(&ParserInput::new(r#""a\nb""#)).map(|r| r.val),
+        Ok(String::from("a\nb"))
+    );
+
+    // Turning escapes off leaves the backslashes as written, whatever the style
+    let verbatim = StringFormat {
+        escapes : false,
+        ..StringFormat::json()
+    };
+    let res = string_literal(verbatim)(&ParserInput::new(r#""a\nb""#));
+    assert_eq!(res.map(|r| r.val), Ok(Cow::Borrowed(r"a\nb")));
+}
+
+#[test]
+fn string_literal_errors_test()
+{
+    let double = string_literal(StringFormat::double());
+    let err_col = |text| {
+        double(&ParserInput::new(text))
+            .map(|r| r.val.into_owned())
+            .map_err(|e| e.pos.column)
+    };
+
+    // Errors point at the backslash of the bad escape
+    assert_eq!(err_col(r#""ok \q""#), Err(4));
+    assert_eq!(err_col(r#""\xZZ""#), Err(1));
+    assert_eq!(err_col(r#""ab\u{110000}""#), Err(3));
+    assert_eq!(err_col(r#""\u{}""#), Err(1));
+    assert_eq!(err_col(r#""é\ud83d""#), Err(2));
+    assert_eq!(err_col(r#""\ud83d\u0041""#), Err(1));
+    assert_eq!(err_col(r#""\ude00""#), Err(1));
+    // Unclosed strings, and newlines within single line strings, fail at the opening quote
+    assert_eq!(err_col(r#""never closed"#), Err(0));
+    assert_eq!(err_col("\"two\nlines\""), Err(0));
+    assert_eq!(err_col("'wrong quote'"), Err(0));
+}
+
+#[test]
+fn string_literal_raw_and_triple_test()
+{
+    let res = string_literal(StringFormat::raw())(&ParserInput::new("r\"C:\\path\\n\nnext\"."));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       Cow::Borrowed("C:\\path\\n\nnext"),
+            pos :       FilePos {
+                line : 2, column : 5
+            },
+            remainder : ".",
+        })
+    );
+
+    let res = string_literal(StringFormat::triple())(&ParserInput::new("\"\"\"say \"hi\"\n\\tthere\"\"\"\"\""));
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       Cow::Owned(String::from("say \"hi\"\n\tthere")),
+            pos :       FilePos {
+                line :   2,
+                column : 10,
+            },
+            remainder : "\"\"",
+        })
+    );
+}
@@ -0,0 +1,142 @@
+use crate::prelude::*;
+use crate::volt_parse::{
+    formats::{json::*, toml::*},
+    tests::{fixture_dir, fixtures},
+};
+
+// The toml-test cases of a kind, as bytes since some of the invalid ones are not UTF-8
+fn toml_fixtures(kind : &str) -> Vec<(String, Vec<u8>)> { fixtures(&format!("toml/{kind}"), "toml") }
+
+// Whether the value matches the expected JSON, where each value other than a table or array is written as an object
+// of its type and its value as a string
+fn matches(expected : &JsonValue, actual : &TomlValue) -> bool
+{
+    match (expected, actual)
+    {
+        (JsonValue::Object(members), _) if members.len() == 2 && expected.get("type").is_some() =>
+        {
+            let kind = expected.get("type").and_then(|t| t.as_str()).unwrap();
+            let value = expected.get("value").and_then(|v| v.as_str()).unwrap();
+            match (kind, actual)
+            {
+                ("string", TomlValue::String(s)) => s == value,
+                ("integer", TomlValue::Integer(i)) => value.parse() == Ok(*i),
+                ("float", TomlValue::Float(f)) =>
+                {
+                    let e = value.parse::<f64>().unwrap();
+                    if e.is_nan()
+                    {
+                        f.0.is_nan()
+                    }
+                    else
+                    {
+                        f.0 == e
+                    }
+                },
+                ("bool", TomlValue::Boolean(b)) => value.parse() == Ok(*b),
+                ("datetime", TomlValue::DateTime(d)) => d.offset.is_some() && d.to_string() == value,
+                ("datetime-local", TomlValue::DateTime(d)) => d.offset.is_none() && d.to_string() == value,
+                ("date-local", TomlValue::LocalDate(d)) => d.to_string() == value,
+                ("time-local", TomlValue::LocalTime(t)) => t.to_string() == value,
+                _ => false,
+            }
+        },
+        (JsonValue::Object(members), TomlValue::Table(table)) =>
+            members.len() == table.entries.len() &&
+                members
+                    .iter()
+                    .all(|(k, v)| table.get(&k.val).is_some_and(|a| matches(&v.val, a))),
+        (JsonValue::Array(items), TomlValue::Array(values)) =>
+            items.len() == values.len() && items.iter().zip(values).all(|(e, a)| matches(&e.val, &a.val)),
+        _ => false,
+    }
+}
+
+#[test]
+fn toml_valid_fixtures_test()
+{
+    let dir = fixture_dir("toml/valid");
+    let mut failed = vec![];
+    for (name, bytes) in toml_fixtures("valid")
+    {
+        let text = String::from_utf8(bytes).unwrap();
+        let expected = parse_json(&std::fs::read_to_string(format!("{dir}/{name}.json")).unwrap()).unwrap();
+        match parse_toml(&text)
+        {
+            Ok(table) if matches(&expected.val, &TomlValue::Table(table.clone())) => (),
+            res => failed.push(format!("{name}: {res:?}")),
+        }
+    }
+
+    println!("{:#?}", failed);
+
+    assert!(failed.is_empty());
+}
+
+#[test]
+fn toml_invalid_fixtures_test()
+{
+    let failed = toml_fixtures("invalid")
+        .into_iter()
+        // Text that is not UTF-8 is rejected before it reaches the parser
+        .filter(|(_, bytes)| std::str::from_utf8(bytes).is_ok_and(|text| parse_toml(text).is_ok()))
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+
+    println!("{:#?}", failed);
+
+    assert!(failed.is_empty());
+}
+
+#[test]
+fn toml_value_test()
+{
+    let res = parse_toml("[package]\nname = \"volt\"\nversion.major = 1\n\n[[bin]]\npath = 'src/main.rs'\n");
+
+    println!("{:#?}", res);
+
+    let doc = res.unwrap();
+    assert_eq!(
+        doc.get_path(&["package", "name"]).and_then(|v| v.as_str()),
+        Some("volt")
+    );
+    assert_eq!(
+        doc.get_path(&["package", "version", "major"])
+            .and_then(|v| v.as_integer()),
+        Some(1)
+    );
+    assert_eq!(doc.get_path(&["package", "missing"]), None);
+    let bins = doc.get("bin").and_then(|v| v.as_array()).unwrap();
+    assert_eq!(bins.len(), 1);
+    assert_eq!(bins[0].span, Span::new(FilePos::new(5, 0), FilePos::new(5, 7)));
+    assert_eq!(
+        bins[0]
+            .val
+            .as_table()
+            .and_then(|t| t.get("path"))
+            .and_then(|v| v.as_str()),
+        Some("src/main.rs")
+    );
+
+    let (key, value) = doc.entries[0].1.val.as_table().unwrap().entry("name").unwrap();
+    assert_eq!(key.span, Span::new(FilePos::new(2, 0), FilePos::new(2, 4)));
+    assert_eq!(value.span, Span::new(FilePos::new(2, 7), FilePos::new(2, 13)));
+}
+
+#[test]
+fn toml_error_position_test()
+{
+    let err = |text : &str| parse_toml(text).unwrap_err().pos;
+
+    // A key defined twice fails at its second definition
+    assert_eq!(err("a = 1\nb = 2\na = 3\n"), FilePos::new(3, 0));
+    assert_eq!(err("[a]\nb.c = 1\n[a.b]\n"), FilePos::new(3, 3));
+    // Values fail at the field or character that is wrong
+    assert_eq!(err("d = 2023-02-29\n"), FilePos::new(1, 12));
+    assert_eq!(err("n = 007\n"), FilePos::new(1, 5));
+    assert_eq!(err("n = 9223372036854775808\n"), FilePos::new(1, 4));
+    assert_eq!(err("s = 'a\u{7}b'\n"), FilePos::new(1, 6));
+    assert_eq!(err("t = { a = 1, }\n"), FilePos::new(1, 13));
+    assert_eq!(err("x = [1, 2\ny = 3\n"), FilePos::new(2, 0));
+    assert_eq!(err("a = 1 # ok\u{0}\n"), FilePos::new(1, 10));
+}
@@ -0,0 +1,62 @@
+use crate::{
+    prelude::*,
+    volt_parse::paths::{current_parser, name_parser, up_parser},
+};
+
+fn traced_piece<'a>() -> impl Parser<'a, Vec<String>>
+{
+    one_or_many(trace(
+        "piece",
+        or(
+            or(trace("up", display(up_parser())), trace("name", display(name_parser()))),
+            trace("current", display(current_parser())),
+        ),
+    ))
+}
+
+#[test]
+fn trace_tree_test()
+{
+    let (res, trace) = with_trace(|| traced_piece()(&ParserInput::new("ab/")));
+
+    println!("{}", trace);
+
+    assert_eq!(res.map(|r| r.remainder), Ok("/"));
+    assert_eq!(
+        trace.to_string(),
+        [
+            "> piece 1:0",
+            "  > up 1:0",
+            "  < up failed 1:0",
+            "  > name 1:0",
+            "  < name ok 1:2 \"ab\"",
+            "< piece ok 1:2 \"ab\"",
+            "> piece 1:2",
+            "  > up 1:2",
+            "  < up failed 1:2",
+            "  > name 1:2",
+            "  < name failed 1:2",
+            "  > current 1:2",
+            "  < current failed 1:2",
+            "< piece failed 1:2",
+            "",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn trace_json_test()
+{
+    let (_, traced) = with_trace(|| trace("quote", keyword("\"a\""))(&ParserInput::new("\"a\"")));
+
+    assert_eq!(
+        traced.to_json(),
+        r#"[{"name":"quote","start":{"line":1,"column":0},"end":{"line":1,"column":3},"success":true,"consumed":"\"a\"","children":[]}]"#
+    );
+
+    // Nothing is recorded outside of `with_trace`
+    let (_, empty) = with_trace(|| ());
+    assert_eq!(empty, Trace::default());
+    assert!(trace("quote", keyword("a"))(&ParserInput::new("a")).is_ok());
+}
@@ -0,0 +1,114 @@
+use crate::prelude::*;
+
+fn c_like() -> Trivia
+{
+    Trivia::whitespace()
+        .with_line_comment("//")
+        .with_block_comment("/*", "*/")
+}
+
+fn word<'a>() -> impl Parser<'a, String> { mod_val(one_or_many(ascii_alpha()), |cs| cs.into_iter().collect()) }
+
+#[test]
+fn lexeme_test()
+{
+    let words = then(
+        trivia(c_like()),
+        none_or_many(lexeme_with(c_like(), word())),
+        take_right,
+    );
+    let res = words(&ParserInput::new("  // lead\none /* two */ three//x\n\n  four;"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       vec![String::from("one"), String::from("three"), String::from("four"),],
+            pos :       FilePos {
+                line : 4, column : 6
+            },
+            remainder : ";",
+        })
+    );
+    // Plain lexemes only skip whitespace
+    assert_eq!(
+        none_or_many(lexeme(word()))(&ParserInput::new("a b\n c // d")).map(|r| (r.val.len(), r.remainder)),
+        Ok((3, "// d"))
+    );
+}
+
+#[test]
+fn block_comment_test()
+{
+    let flat = trivia(c_like());
+    let nested = trivia(c_like().with_nested_comments());
+    let text = "/* a /* b */ c */x";
+
+    assert_eq!(flat(&ParserInput::new(text)).map(|r| r.remainder), Ok("c */x"));
+    assert_eq!(nested(&ParserInput::new(text)).map(|r| r.remainder), Ok("x"));
+    // An unclosed block comment fails where it opens
+    assert_eq!(
+        nested(&ParserInput::new(" \n /* a /* b */")),
+        Err(PErr {
+            pos : FilePos {
+                line : 2, column : 1
+            },
+        })
+    );
+}
+
+#[test]
+fn token_test()
+{
+    let res = token_with(c_like(), word())(&ParserInput::new("name /* why */\n= 1"));
+
+    println!("{:#?}", res);
+
+    assert_eq!(
+        res,
+        Ok(PRes {
+            val :       Token {
+                val :    String::from("name"),
+                span :   Span::new(FilePos::new(1, 0), FilePos::new(1, 4)),
+                trivia : vec![
+                    CstToken {
+                        kind : String::from(WHITESPACE_KIND),
+                        text : String::from(" "),
+                        span : Span::new(FilePos::new(1, 4), FilePos::new(1, 5)),
+                    },
+                    CstToken {
+                        kind : String::from(BLOCK_COMMENT_KIND),
+                        text : String::from("/* why */"),
+                        span : Span::new(FilePos::new(1, 5), FilePos::new(1, 14)),
+                    },
+                    CstToken {
+                        kind : String::from(WHITESPACE_KIND),
+                        text : String::from("\n"),
+                        span : Span::new(FilePos::new(1, 14), FilePos::new(2, 0)),
+                    },
+                ],
+            },
+            pos :       FilePos::new(2, 0),
+            remainder : "= 1",
+        })
+    );
+}
+
+#[test]
+fn trivia_cst_test()
+{
+    let text = "a // first\nb";
+    let tree = node("list", none_or_many(lexeme_with(c_like(), leaf("word", word()))))(&ParserInput::new(text))
+        .unwrap()
+        .val;
+
+    println!("{}", tree.dump());
+
+    assert_eq!(tree.text(), text);
+    let kinds = tree.children.iter().map(|c| c.kind()).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec!["word", WHITESPACE_KIND, LINE_COMMENT_KIND, WHITESPACE_KIND, "word"]
+    );
+}
@@ -1,10 +0,0 @@
-pub mod parser;
-pub mod path;
-pub mod tests;
-
-// #[derive(Debug, Clone, Serialize, Deserialize)]
-// pub enum VFSBlock
-// {
-//     File(String),
-//     Dir(BTreeMap<String, VFSRef>),
-// }
@@ -0,0 +1,193 @@
+use super::{
+    file_pos::FilePos,
+    parser::{POut, PResData, Parser, ParserInput},
+    span::consumed,
+};
+use std::{cell::RefCell, fmt::Display};
+
+// A single attempt at parsing a traced rule, along with every traced rule attempted within it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceNode
+{
+    pub name :     String,
+    pub start :    FilePos,
+    // Where the rule finished should it succeed, or where it failed otherwise
+    pub end :      FilePos,
+    pub success :  bool,
+    pub consumed : String,
+    pub children : Vec<TraceNode>,
+}
+
+// Everything traced during a call to `with_trace`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Trace
+{
+    pub roots : Vec<TraceNode>,
+}
+
+thread_local! {
+    // The rules currently being traced, innermost last, with the finished top level rules at the bottom
+    static TRACER : RefCell<Option<Vec<TraceNode>>> = const { RefCell::new(None) };
+}
+
+// Runs `f`, recording every `trace` rule that it attempts to parse
+pub fn with_trace<R>(f : impl FnOnce() -> R) -> (R, Trace)
+{
+    let root = TraceNode {
+        name :     String::new(),
+        start :    FilePos::default(),
+        end :      FilePos::default(),
+        success :  true,
+        consumed : String::new(),
+        children : vec![],
+    };
+    let outer = TRACER.with(|t| t.borrow_mut().replace(vec![root]));
+    let res = f();
+    let stack = TRACER
+        .with(|t| std::mem::replace(&mut *t.borrow_mut(), outer))
+        .unwrap_or_default();
+    let roots = stack.into_iter().next().map(|root| root.children).unwrap_or_default();
+    (
+        res,
+        Trace {
+            roots,
+        },
+    )
+}
+
+// Names `p` so that its entry and exit are recorded while within `with_trace`, otherwise this does nothing at all
+#[inline]
+pub fn trace<'a, DatT : PResData>(name : &'a str, p : impl Parser<'a, DatT>) -> impl Parser<'a, DatT>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, DatT> {
+        let tracing = TRACER.with(|t| match t.borrow_mut().as_mut()
+        {
+            Some(stack) =>
+            {
+                stack.push(TraceNode {
+                    name :     name.to_string(),
+                    start :    ind.pos,
+                    end :      ind.pos,
+                    success :  false,
+                    consumed : String::new(),
+                    children : vec![],
+                });
+                true
+            },
+            None => false,
+        });
+        if !tracing
+        {
+            return p(ind);
+        }
+
+        let res = p(ind);
+
+        TRACER.with(|t| {
+            if let Some(stack) = t.borrow_mut().as_mut()
+            {
+                let mut node = stack.pop().unwrap();
+                match &res
+                {
+                    Ok(succ) =>
+                    {
+                        node.success = true;
+                        node.end = succ.pos;
+                        node.consumed = consumed(ind.text, succ.remainder).to_string();
+                    },
+                    Err(err) => node.end = err.pos,
+                }
+                if let Some(parent) = stack.last_mut()
+                {
+                    parent.children.push(node);
+                }
+            }
+        });
+        res
+    }
+}
+
+impl TraceNode
+{
+    fn write(&self, depth : usize, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let indent = "  ".repeat(depth);
+        writeln!(f, "{indent}> {} {}:{}", self.name, self.start.line, self.start.column)?;
+        for child in &self.children
+        {
+            child.write(depth + 1, f)?;
+        }
+        if self.success
+        {
+            writeln!(
+                f,
+                "{indent}< {} ok {}:{} {:?}",
+                self.name, self.end.line, self.end.column, self.consumed
+            )
+        }
+        else
+        {
+            writeln!(
+                f,
+                "{indent}< {} failed {}:{}",
+                self.name, self.end.line, self.end.column
+            )
+        }
+    }
+
+    pub fn to_json(&self) -> String
+    {
+        format!(
+            r#"{{"name":{},"start":{},"end":{},"success":{},"consumed":{},"children":[{}]}}"#,
+            json_str(&self.name),
+            json_pos(self.start),
+            json_pos(self.end),
+            self.success,
+            json_str(&self.consumed),
+            self.children.iter().map(|c| c.to_json()).collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+impl Trace
+{
+    // The trace as a JSON array of nodes, for inspecting elsewhere
+    pub fn to_json(&self) -> String
+    {
+        format!(
+            "[{}]",
+            self.roots.iter().map(|r| r.to_json()).collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+// An indented tree, with a line as each rule is entered and another as it is exited
+impl Display for Trace
+{
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        self.roots.iter().try_for_each(|r| r.write(0, f))
+    }
+}
+
+fn json_pos(pos : FilePos) -> String { format!(r#"{{"line":{},"column":{}}}"#, pos.line, pos.column) }
+
+pub(crate) fn json_str(s : &str) -> String
+{
+    let mut out = String::from("\"");
+    for c in s.chars()
+    {
+        match c
+        {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,199 @@
+use super::{
+    balanced::nested_len,
+    char_class::CharClass,
+    combiners::take_left,
+    cst::{record, CstElement, CstToken, WHITESPACE_KIND},
+    parser::{PErr, POut, PRes, PResData, Parser, ParserInput},
+    parsers_core::then,
+    span::Span,
+};
+
+// The kinds given to comments read as trivia, alongside `WHITESPACE_KIND` for whitespace
+pub const LINE_COMMENT_KIND : &str = "line_comment";
+pub const BLOCK_COMMENT_KIND : &str = "block_comment";
+
+// The text between tokens that carries no meaning of its own, being whitespace and comments:
+//
+//   let c_like = Trivia::whitespace().with_line_comment("//").with_block_comment("/*", "*/");
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia
+{
+    pub whitespace :     CharClass,
+    // Each runs until the end of the line, leaving the newline itself
+    pub line_comments :  Vec<&'static str>,
+    // Each as its opening and closing text
+    pub block_comments : Vec<(&'static str, &'static str)>,
+    // Whether block comments may contain other block comments, which must then be closed first
+    pub nested :         bool,
+}
+
+impl Trivia
+{
+    pub fn new(whitespace : CharClass) -> Self
+    {
+        Self {
+            whitespace,
+            line_comments : vec![],
+            block_comments : vec![],
+            nested : false,
+        }
+    }
+
+    // Spaces, tabs and newlines
+    pub fn whitespace() -> Self { Self::new(CharClass::chars(" \t\r\n")) }
+
+    // Spaces and tabs only, for grammars where newlines are significant
+    pub fn spaces() -> Self { Self::new(CharClass::chars(" \t")) }
+
+    pub fn with_line_comment(mut self, start : &'static str) -> Self
+    {
+        self.line_comments.push(start);
+        self
+    }
+
+    pub fn with_block_comment(mut self, open : &'static str, close : &'static str) -> Self
+    {
+        self.block_comments.push((open, close));
+        self
+    }
+
+    pub fn with_nested_comments(mut self) -> Self
+    {
+        self.nested = true;
+        self
+    }
+
+    // The kind and length of the piece of trivia that `text` begins with, or `Err` if it begins with a block comment
+    // that is never closed
+    fn piece(&self, text : &str) -> Result<Option<(&'static str, usize)>, ()>
+    {
+        let ws = text.find(|c| !self.whitespace.contains(c)).unwrap_or(text.len());
+        if ws > 0
+        {
+            return Ok(Some((WHITESPACE_KIND, ws)));
+        }
+        if self
+            .line_comments
+            .iter()
+            .any(|start| !start.is_empty() && text.starts_with(start))
+        {
+            return Ok(Some((LINE_COMMENT_KIND, text.find('\n').unwrap_or(text.len()))));
+        }
+        match self
+            .block_comments
+            .iter()
+            .find(|(open, _)| !open.is_empty() && text.starts_with(open))
+        {
+            Some((open, close)) => self
+                .block_len(text, open, close)
+                .map(|len| Some((BLOCK_COMMENT_KIND, len)))
+                .ok_or(()),
+            None => Ok(None),
+        }
+    }
+
+    fn block_len(&self, text : &str, open : &str, close : &str) -> Option<usize>
+    {
+        if self.nested
+        {
+            nested_len(text, open, close)
+        }
+        else
+        {
+            text[open.len()..].find(close).map(|end| open.len() + end + close.len())
+        }
+    }
+}
+
+// A value parsed by `token_with`, along with the trivia that followed it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<DatT>
+{
+    pub val :    DatT,
+    // Where the value itself was parsed from, not including the trivia
+    pub span :   Span,
+    pub trivia : Vec<CstToken>,
+}
+
+// Any amount of trivia, including none at all, as a token for each run of whitespace and each comment. Within a CST
+// `node` these become its tokens, rather than the whitespace and text tokens that unclaimed text would become.
+//
+// Fails at the start of a block comment that is never closed.
+#[inline]
+pub fn trivia<'a>(trivia_def : Trivia) -> impl Parser<'a, Vec<CstToken>>
+{
+    move |ind : &ParserInput<'a>| -> POut<'a, Vec<CstToken>> {
+        let mut pieces = vec![];
+        let mut pos = ind.pos;
+        let mut i = 0;
+        loop
+        {
+            let rest = &ind.text[i..];
+            let (kind, len) = match trivia_def.piece(rest)
+            {
+                Ok(Some(piece)) => piece,
+                Ok(None) => break,
+                Err(()) =>
+                    return Err(PErr {
+                        pos,
+                    }),
+            };
+            let end = pos.advance(&rest[..len]);
+            let token = CstToken {
+                kind : String::from(kind),
+                text : String::from(&rest[..len]),
+                span : Span::new(pos, end),
+            };
+            record(rest.len(), rest.len() - len, CstElement::Token(token.clone()));
+            pieces.push(token);
+            pos = end;
+            i += len;
+        }
+        Ok(PRes {
+            val : pieces,
+            pos,
+            remainder : &ind.text[i..],
+        })
+    }
+}
+
+// Skips any whitespace after `p`, see `lexeme_with` to also skip comments
+#[inline]
+pub fn lexeme<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parser<'a, DatT>
+{
+    lexeme_with(Trivia::whitespace(), p)
+}
+
+// Skips any trivia after `p`, so that a grammar built from lexemes only needs to skip the trivia at its very start
+#[inline]
+pub fn lexeme_with<'a, DatT : PResData>(trivia_def : Trivia, p : impl Parser<'a, DatT>) -> impl Parser<'a, DatT>
+{
+    then(p, trivia(trivia_def), take_left)
+}
+
+// Like `lexeme`, keeping the whitespace that was skipped
+#[inline]
+pub fn token<'a, DatT : PResData>(p : impl Parser<'a, DatT>) -> impl Parser<'a, Token<DatT>>
+{
+    token_with(Trivia::whitespace(), p)
+}
+
+// Like `lexeme_with`, keeping the trivia that was skipped so that the text can be reproduced exactly
+#[inline]
+pub fn token_with<'a, DatT : PResData>(trivia_def : Trivia, p : impl Parser<'a, DatT>) -> impl Parser<'a, Token<DatT>>
+{
+    let skip = trivia(trivia_def);
+    move |ind : &ParserInput<'a>| -> POut<'a, Token<DatT>> {
+        let res = p(ind)?;
+        let after = skip(&res.to_in())?;
+        Ok(PRes {
+            val :       Token {
+                val :    res.val,
+                span :   Span::new(ind.pos, res.pos),
+                trivia : after.val,
+            },
+            pos :       after.pos,
+            remainder : after.remainder,
+        })
+    }
+}
@@ -0,0 +1,99 @@
+use super::{
+    char_class::{char_class, CharClass},
+    combiner::smcomb,
+    parser::{PErr, POut, PRes, Parser, ParserInput},
+    parsers_core::{none_or_many, then},
+};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+// How text is normalized before it is compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Normalization
+{
+    // Canonical equivalence, so precomposed and decomposed accents match each other
+    Nfc,
+    // Compatibility equivalence, which also matches ligatures, full width forms and the like to their plain forms
+    Nfkc,
+}
+
+impl Normalization
+{
+    pub fn normalize(&self, text : &str) -> String
+    {
+        match self
+        {
+            Normalization::Nfc => text.nfc().collect(),
+            Normalization::Nfkc => text.nfkc().collect(),
+        }
+    }
+}
+
+// Like `keyword`, ignoring case using full Unicode case folding, so "STRASSE" matches "straße"
+#[inline]
+pub fn keyword_fold<'a>(word : &'a str) -> impl Parser<'a, String> { keyword_by(word, caseless::default_case_fold_str) }
+
+// Like `keyword`, comparing the word and the input once both are normalized
+#[inline]
+pub fn keyword_normalized<'a>(word : &'a str, form : Normalization) -> impl Parser<'a, String>
+{
+    keyword_by(word, move |text| form.normalize(text))
+}
+
+// Like `keyword_fold`, comparing the word and the input once both are normalized
+#[inline]
+pub fn keyword_fold_normalized<'a>(word : &'a str, form : Normalization) -> impl Parser<'a, String>
+{
+    // The canonical and compatibility caseless matching described by the Unicode standard
+    keyword_by(word, move |text| match form
+    {
+        Normalization::Nfc => caseless::default_case_fold_str(&text.nfd().collect::<String>())
+            .nfd()
+            .collect(),
+        Normalization::Nfkc =>
+        {
+            let folded = caseless::default_case_fold_str(&text.nfd().collect::<String>());
+            caseless::default_case_fold_str(&folded.nfkd().collect::<String>())
+                .nfkd()
+                .collect()
+        },
+    })
+}
+
+// An identifier as defined by UAX #31, an XID_Start character followed by any number of XID_Continue characters
+#[inline]
+pub fn unicode_identifier<'a>() -> impl Parser<'a, String>
+{
+    then(
+        char_class(CharClass::XidStart),
+        none_or_many(char_class(CharClass::XidContinue)),
+        smcomb(|first : char, rest : Vec<char>| std::iter::once(first).chain(rest).collect::<String>()),
+    )
+}
+
+// Matches the shortest input that is the same as `word` once both are transformed, returning the text as it appears in
+// the input. A match may not end part way through a character, so it may not be followed by a combining mark.
+fn keyword_by<'a>(word : &'a str, transform : impl Fn(&str) -> String + Clone) -> impl Parser<'a, String>
+{
+    let target = transform(word);
+    // Transforming text can change its length, though never by this much
+    let max_chars = word.chars().count() * 4 + 4;
+    move |ind : &ParserInput<'a>| -> POut<'a, String> {
+        let ends = ind.text.char_indices().map(|(i, _)| i).skip(1).chain([ind.text.len()]);
+        for end in ends.take(max_chars)
+        {
+            let matched = &ind.text[..end];
+            let at_boundary = ind.text[end..].chars().next().is_none_or(|c| !is_combining_mark(c));
+            if at_boundary && transform(matched) == target
+            {
+                return Ok(PRes {
+                    val :       String::from(matched),
+                    pos :       ind.pos.advance(matched),
+                    remainder : &ind.text[end..],
+                });
+            }
+        }
+        Err(PErr {
+            pos : ind.pos
+        })
+    }
+}
@@ -0,0 +1,259 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Error, Fields, GenericArgument, LitStr, Path, PathArguments, Result, Type};
+
+// The parsing related attributes that may be attached to a type, variant or field
+#[derive(Default)]
+struct Attrs
+{
+    keyword : Option<LitStr>,
+    sep :     Option<LitStr>,
+    skip_ws : bool,
+    parser :  Option<Path>,
+}
+
+fn read_attrs(attrs : &[Attribute]) -> Result<Attrs>
+{
+    let mut out = Attrs::default();
+    for attr in attrs
+    {
+        if attr.path().is_ident("keyword")
+        {
+            out.keyword = Some(attr.parse_args()?);
+        }
+        else if attr.path().is_ident("sep")
+        {
+            out.sep = Some(attr.parse_args()?);
+        }
+        else if attr.path().is_ident("skip_ws")
+        {
+            attr.meta.require_path_only()?;
+            out.skip_ws = true;
+        }
+        else if attr.path().is_ident("parser")
+        {
+            out.parser = Some(attr.parse_args()?);
+        }
+    }
+    Ok(out)
+}
+
+pub fn expand(input : DeriveInput) -> Result<TokenStream>
+{
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let top = read_attrs(&input.attrs)?;
+
+    let body = match &input.data
+    {
+        Data::Struct(data) => seq_body(quote!(Self), &data.fields, top.keyword.as_ref(), top.skip_ws)?,
+        Data::Enum(data) =>
+        {
+            if data.variants.is_empty()
+            {
+                return Err(Error::new_spanned(
+                    name,
+                    "VoltParse cannot be derived for an enum without variants",
+                ));
+            }
+
+            let mut variants = vec![];
+            for variant in &data.variants
+            {
+                let attrs = read_attrs(&variant.attrs)?;
+                if attrs.sep.is_some() || attrs.parser.is_some()
+                {
+                    return Err(Error::new_spanned(
+                        variant,
+                        "#[sep] and #[parser] may only be used on fields",
+                    ));
+                }
+                let ident = &variant.ident;
+                variants.push(seq_body(
+                    quote!(Self::#ident),
+                    &variant.fields,
+                    attrs.keyword.as_ref(),
+                    top.skip_ws || attrs.skip_ws,
+                )?);
+            }
+
+            // Variants are attempted in the order that they are declared, just like `or`, so whatever a failed variant
+            // records for the CST is rolled back
+            let closures = (0..variants.len())
+                .map(|i| format_ident!("__volt_variant_{}", i))
+                .collect::<Vec<_>>();
+            let first = &closures[0];
+            let rest = &closures[1..];
+            quote! {
+                #(
+                    let #closures = |ind : &volt_parse::ParserInput<'a>| -> volt_parse::POut<'a, Self> #variants;
+                )*
+                volt_parse::attempt(|| #first(ind))#(.or_else(|_| volt_parse::attempt(|| #rest(ind))))*
+            }
+        },
+        Data::Union(_) => return Err(Error::new_spanned(name, "VoltParse cannot be derived for unions")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics volt_parse::VoltParse for #name #ty_generics #where_clause
+        {
+            fn parser<'a>() -> impl volt_parse::Parser<'a, Self>
+            {
+                move |ind : &volt_parse::ParserInput<'a>| -> volt_parse::POut<'a, Self> {
+                    #body
+                }
+            }
+        }
+    })
+}
+
+// Generates a block that parses an optional leading keyword followed by each field in order, and then constructs the
+// value
+fn seq_body(ctor : TokenStream, fields : &Fields, keyword : Option<&LitStr>, skip_ws : bool) -> Result<TokenStream>
+{
+    let ws = skip_ws_step(skip_ws);
+
+    let leading = keyword.map(|kw| {
+        quote! {
+            #ws
+            let cur = volt_parse::keyword(#kw)(&cur)?.to_in();
+        }
+    });
+
+    let mut steps = vec![];
+    let mut names = vec![];
+    for (i, field) in fields.iter().enumerate()
+    {
+        let attrs = read_attrs(&field.attrs)?;
+        let skip = skip_ws || attrs.skip_ws;
+        let parser = field_parser(field, &attrs, skip)?;
+        let name = format_ident!("__volt_f{}", i);
+        let ws = skip_ws_step(skip);
+        steps.push(quote! {
+            #ws
+            let res = (#parser)(&cur)?;
+            let cur = res.to_in();
+            let #name = res.val;
+        });
+        names.push(name);
+    }
+
+    let value = match fields
+    {
+        Fields::Named(named) =>
+        {
+            let idents = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!(#ctor { #(#idents : #names),* })
+        },
+        Fields::Unnamed(_) => quote!(#ctor(#(#names),*)),
+        Fields::Unit => ctor,
+    };
+
+    Ok(quote! {
+        {
+            let cur = *ind;
+            #leading
+            #(#steps)*
+            Ok(volt_parse::PRes {
+                val :       #value,
+                pos :       cur.pos,
+                remainder : cur.text,
+            })
+        }
+    })
+}
+
+fn skip_ws_step(skip_ws : bool) -> Option<TokenStream>
+{
+    skip_ws.then(|| quote!(let cur = volt_parse::many_air()(&cur)?.to_in();))
+}
+
+fn field_parser(field : &syn::Field, attrs : &Attrs, skip_ws : bool) -> Result<TokenStream>
+{
+    if let Some(path) = &attrs.parser
+    {
+        return Ok(quote!(#path()));
+    }
+    if let Some(kw) = &attrs.keyword
+    {
+        return Ok(quote!(volt_parse::keyword(#kw)));
+    }
+
+    match (generic_arg("Vec", &field.ty), &attrs.sep)
+    {
+        (Some(elem), sep) =>
+        {
+            let elem = ws_prefixed(type_parser(elem), skip_ws);
+            Ok(match sep
+            {
+                Some(sep) =>
+                {
+                    let sep = ws_prefixed(quote!(volt_parse::keyword(#sep)), skip_ws);
+                    quote!(volt_parse::sep_by(#elem, #sep))
+                },
+                None => quote!(volt_parse::none_or_many(#elem)),
+            })
+        },
+        (None, Some(sep)) => Err(Error::new_spanned(sep, "#[sep] may only be used on a Vec field")),
+        (None, None) => Ok(type_parser(&field.ty)),
+    }
+}
+
+fn ws_prefixed(parser : TokenStream, skip_ws : bool) -> TokenStream
+{
+    if skip_ws
+    {
+        quote!(volt_parse::then(volt_parse::many_air(), #parser, volt_parse::take_right))
+    }
+    else
+    {
+        parser
+    }
+}
+
+fn type_parser(ty : &Type) -> TokenStream
+{
+    if let Some(inner) = generic_arg("Vec", ty)
+    {
+        let inner = type_parser(inner);
+        quote!(volt_parse::none_or_many(#inner))
+    }
+    else if let Some(inner) = generic_arg("Option", ty)
+    {
+        let inner = type_parser(inner);
+        quote!(volt_parse::maybe(#inner))
+    }
+    else if let Some(inner) = generic_arg("Box", ty)
+    {
+        let inner = type_parser(inner);
+        quote!(volt_parse::mod_val(#inner, ::std::boxed::Box::new))
+    }
+    else
+    {
+        quote!(<#ty as volt_parse::VoltParse>::parser())
+    }
+}
+
+// Returns `T` if `ty` is `wrapper<T>`
+fn generic_arg<'t>(wrapper : &str, ty : &'t Type) -> Option<&'t Type>
+{
+    let Type::Path(path) = ty
+    else
+    {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    if last.ident != wrapper
+    {
+        return None;
+    }
+    match &last.arguments
+    {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0]
+        {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,372 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    braced,
+    bracketed,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    token,
+    Block,
+    Ident,
+    LitChar,
+    LitStr,
+    Pat,
+    Path,
+    Result,
+    Token,
+    Type,
+    Visibility,
+};
+
+// `vis name -> Type = expr ;`
+struct RuleDef
+{
+    vis :  Visibility,
+    name : Ident,
+    ty :   Type,
+    expr : Choice,
+}
+
+// `alt / alt / ...`
+struct Choice(Vec<Alt>);
+
+// `item item ... { action }`
+struct Alt
+{
+    items :  Vec<Item>,
+    action : Option<Block>,
+}
+
+// `label:&primary*`
+struct Item
+{
+    label :   Option<Ident>,
+    prefix :  Option<char>,
+    primary : Primary,
+    suffix :  Option<char>,
+}
+
+enum Primary
+{
+    Keyword(LitStr),
+    Char(LitChar),
+    Rule(Path),
+    Embed(syn::Expr),
+    Group(Choice),
+    Class
+    {
+        negated : bool,
+        pat :     Pat,
+    },
+    Any,
+}
+
+pub struct Grammar(Vec<RuleDef>);
+
+impl Parse for Grammar
+{
+    fn parse(input : ParseStream) -> Result<Self>
+    {
+        let mut rules = vec![];
+        while !input.is_empty()
+        {
+            rules.push(input.parse()?);
+        }
+        Ok(Self(rules))
+    }
+}
+
+impl Parse for RuleDef
+{
+    fn parse(input : ParseStream) -> Result<Self>
+    {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![->]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let expr = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(Self {
+            vis,
+            name,
+            ty,
+            expr,
+        })
+    }
+}
+
+impl Parse for Choice
+{
+    fn parse(input : ParseStream) -> Result<Self>
+    {
+        let mut alts = vec![input.parse()?];
+        while input.peek(Token![/])
+        {
+            input.parse::<Token![/]>()?;
+            alts.push(input.parse()?);
+        }
+        Ok(Self(alts))
+    }
+}
+
+impl Parse for Alt
+{
+    fn parse(input : ParseStream) -> Result<Self>
+    {
+        let mut items = vec![];
+        while !(input.is_empty() || input.peek(Token![/]) || input.peek(Token![;]) || input.peek(token::Brace))
+        {
+            items.push(input.parse()?);
+        }
+        let action = if input.peek(token::Brace)
+        {
+            Some(input.parse()?)
+        }
+        else
+        {
+            None
+        };
+        Ok(Self {
+            items,
+            action,
+        })
+    }
+}
+
+impl Parse for Item
+{
+    fn parse(input : ParseStream) -> Result<Self>
+    {
+        let label = if input.peek(Ident) && input.peek2(Token![:]) && !input.peek2(Token![::])
+        {
+            let label = input.parse()?;
+            input.parse::<Token![:]>()?;
+            Some(label)
+        }
+        else
+        {
+            None
+        };
+
+        let prefix = if input.peek(Token![&])
+        {
+            input.parse::<Token![&]>()?;
+            Some('&')
+        }
+        else if input.peek(Token![!])
+        {
+            input.parse::<Token![!]>()?;
+            Some('!')
+        }
+        else
+        {
+            None
+        };
+
+        let primary = input.parse()?;
+
+        let suffix = if input.peek(Token![*])
+        {
+            input.parse::<Token![*]>()?;
+            Some('*')
+        }
+        else if input.peek(Token![+])
+        {
+            input.parse::<Token![+]>()?;
+            Some('+')
+        }
+        else if input.peek(Token![?])
+        {
+            input.parse::<Token![?]>()?;
+            Some('?')
+        }
+        else
+        {
+            None
+        };
+
+        Ok(Self {
+            label,
+            prefix,
+            primary,
+            suffix,
+        })
+    }
+}
+
+impl Parse for Primary
+{
+    fn parse(input : ParseStream) -> Result<Self>
+    {
+        if input.peek(LitStr)
+        {
+            Ok(Primary::Keyword(input.parse()?))
+        }
+        else if input.peek(LitChar)
+        {
+            Ok(Primary::Char(input.parse()?))
+        }
+        else if input.peek(token::Paren)
+        {
+            let content;
+            parenthesized!(content in input);
+            Ok(Primary::Group(content.parse()?))
+        }
+        else if input.peek(token::Bracket)
+        {
+            let content;
+            bracketed!(content in input);
+            let negated = content.peek(Token![^]);
+            if negated
+            {
+                content.parse::<Token![^]>()?;
+            }
+            Ok(Primary::Class {
+                negated,
+                pat : Pat::parse_multi_with_leading_vert(&content)?,
+            })
+        }
+        else if input.peek(Token![.])
+        {
+            input.parse::<Token![.]>()?;
+            Ok(Primary::Any)
+        }
+        else if input.peek(Token![@])
+        {
+            input.parse::<Token![@]>()?;
+            let content;
+            braced!(content in input);
+            Ok(Primary::Embed(content.parse()?))
+        }
+        else
+        {
+            Ok(Primary::Rule(Path::parse_mod_style(input)?))
+        }
+    }
+}
+
+pub fn expand(grammar : Grammar) -> TokenStream
+{
+    let rules = grammar.0.iter().map(|rule| {
+        let RuleDef {
+            vis,
+            name,
+            ty,
+            expr,
+        } = rule;
+        let body = choice(expr);
+        quote! {
+            #vis fn #name<'a>() -> impl volt_parse::Parser<'a, #ty>
+            {
+                move |ind : &volt_parse::ParserInput<'a>| -> volt_parse::POut<'a, #ty> { (#body)(ind) }
+            }
+        }
+    });
+    quote!(#(#rules)*)
+}
+
+fn choice(c : &Choice) -> TokenStream
+{
+    let mut alts = c.0.iter().rev().map(alt);
+    let last = alts.next().unwrap();
+    alts.fold(last, |rest, a| quote!(volt_parse::or(#a, #rest)))
+}
+
+// Sequences are built out of nested `then`s, whose nested tuple output is then destructured for the action
+fn alt(a : &Alt) -> TokenStream
+{
+    let parsers = a.items.iter().map(item).collect::<Vec<_>>();
+    let names = a
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, it)| it.label.clone().unwrap_or_else(|| format_ident!("__volt_v{}", i)))
+        .collect::<Vec<_>>();
+    let labels = a.items.iter().map(|it| match &it.label
+    {
+        Some(label) => quote!(#label),
+        None => quote!(_),
+    });
+
+    let (seq, pattern) = match parsers.len()
+    {
+        0 => (quote!(volt_parse::always(|| ())), quote!(_)),
+        _ =>
+        {
+            let mut parsers = parsers.iter().rev();
+            let mut pats = labels.rev();
+            let last = (parsers.next().unwrap().clone(), pats.next().unwrap());
+            parsers.zip(pats).fold(last, |(seq, pat), (p, l)| {
+                (
+                    quote!(volt_parse::then(#p, #seq, volt_parse::left_right)),
+                    quote!((#l, #pat)),
+                )
+            })
+        },
+    };
+
+    match &a.action
+    {
+        Some(action) =>
+        {
+            let stmts = &action.stmts;
+            quote!(volt_parse::mod_val(#seq, move |#pattern| { #(#stmts)* }))
+        },
+        None if parsers.len() > 1 =>
+        {
+            // Flattens the nested tuple into a single tuple of every value
+            let mut ns = names.iter().rev();
+            let last = ns.next().unwrap();
+            let nested = ns.fold(quote!(#last), |pat, n| quote!((#n, #pat)));
+            quote!(volt_parse::mod_val(#seq, |#nested| (#(#names),*)))
+        },
+        None => seq,
+    }
+}
+
+fn item(it : &Item) -> TokenStream
+{
+    let p = primary(&it.primary);
+    let p = match it.suffix
+    {
+        Some('*') => quote!(volt_parse::none_or_many(#p)),
+        Some('+') => quote!(volt_parse::one_or_many(#p)),
+        Some(_) => quote!(volt_parse::maybe(#p)),
+        None => p,
+    };
+    match it.prefix
+    {
+        Some('&') => quote!(volt_parse::no_consume(#p)),
+        Some(_) => quote!(volt_parse::not(volt_parse::mod_val(#p, |_| ()), || ())),
+        None => p,
+    }
+}
+
+fn primary(p : &Primary) -> TokenStream
+{
+    match p
+    {
+        Primary::Keyword(lit) => quote!(volt_parse::keyword(#lit)),
+        Primary::Char(lit) => quote!(volt_parse::char_single(#lit)),
+        Primary::Rule(path) => quote!(#path()),
+        Primary::Embed(expr) => quote!(#expr),
+        Primary::Group(c) => choice(c),
+        Primary::Class {
+            negated,
+            pat,
+        } =>
+        {
+            let test = quote!(matches!(c, #pat));
+            if *negated
+            {
+                quote!(volt_parse::read_char_f(|c| !#test))
+            }
+            else
+            {
+                quote!(volt_parse::read_char_f(|c| #test))
+            }
+        },
+        Primary::Any => quote!(volt_parse::any_char()),
+    }
+}
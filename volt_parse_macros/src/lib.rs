@@ -0,0 +1,43 @@
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod derive;
+mod grammar;
+
+// Generates a `VoltParse` implementation that parses the annotated type.
+//
+// * `#[keyword("..")]` on a unit variant matches that keyword, on a struct or variant with fields it matches the
+//   keyword before the fields, and on a `String` field it parses the keyword into that field
+// * `#[sep("..")]` on a `Vec<T>` field separates the elements with the given keyword
+// * `#[skip_ws]` on a type, variant or field skips any whitespace before each keyword and field within it
+// * `#[parser(path)]` on a field parses it with `path()` instead of the field type's own parser
+#[proc_macro_derive(VoltParse, attributes(keyword, sep, skip_ws, parser))]
+pub fn derive_volt_parse(input : TokenStream) -> TokenStream
+{
+    let input = parse_macro_input!(input as DeriveInput);
+    derive::expand(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+// Expands PEG-style rules into functions built out of the core combinators.
+//
+//   grammar! {
+//       pub sum -> i64 = l:product "+" r:sum { l + r } / product ;
+//       product -> i64 = digits:['0'..='9']+ { digits.into_iter().collect::<String>().parse().unwrap() } ;
+//   }
+//
+// * `"text"` is a `keyword`, `'c'` is a `char_single` and `.` is `any_char`
+// * `rule` calls the zero argument parser function `rule()`, which may be another rule or any existing parser
+// * `@{ expr }` uses the parser that the Rust expression `expr` evaluates to
+// * `[pattern]` matches a single char that matches the pattern (as in `matches!`), `[^pattern]` one that does not
+// * `a b` is `then`, `a / b` is `or`, `( .. )` groups, `a*` is `none_or_many`, `a+` is `one_or_many`, `a?` is `maybe`,
+//   `&a` is `no_consume` and `!a` is `not`
+// * `label:a` binds the value of `a` for use within the action `{ .. }` that ends the alternative, which becomes a
+//   `mod_val`. Without an action a sequence produces a tuple of every value, or just the value of a single item
+#[proc_macro]
+pub fn grammar(input : TokenStream) -> TokenStream
+{
+    let input = parse_macro_input!(input as grammar::Grammar);
+    grammar::expand(input).into()
+}